@@ -0,0 +1,56 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::{BuildCS, CS},
+        eddsaposeidon::c_eddsaposeidon_verify,
+        lc::{AbstractLC, VecLC, LC},
+        num::CNum,
+        poseidon::{c_poseidon_merkle_proof_root, CMerkleProof},
+    },
+    core::signal::Signal,
+    engines::bn256::{Fr, JubJubBN256},
+    native::poseidon::PoseidonParams,
+};
+
+fn merkle_proof<L: AbstractLC<Fr>>(poseidon_params: &PoseidonParams<Fr>) -> usize {
+    let ref rcs = BuildCS::<Fr, L>::rc_new_with_lc();
+    let root = CNum::alloc(rcs, None);
+    root.inputize();
+    let (leaf, proof): (CNum<_>, CMerkleProof<_, 32>) = Signal::alloc(rcs, None);
+    c_poseidon_merkle_proof_root(&leaf, &proof, poseidon_params).assert_eq(&root);
+    let num_gates = rcs.borrow().num_gates();
+    num_gates
+}
+
+fn eddsa<L: AbstractLC<Fr>>(poseidon_params: &PoseidonParams<Fr>, jubjub_params: &JubJubBN256) -> usize {
+    let ref rcs = BuildCS::<Fr, L>::rc_new_with_lc();
+    let m = CNum::alloc(rcs, None);
+    m.inputize();
+    let (s, r, a): (CNum<_>, CNum<_>, CNum<_>) = Signal::alloc(rcs, None);
+    c_eddsaposeidon_verify(&s, &r, &a, &m, poseidon_params, jubjub_params).assert_const(&true);
+    let num_gates = rcs.borrow().num_gates();
+    num_gates
+}
+
+fn bench_lc(c: &mut Criterion) {
+    let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+    let eddsa_poseidon_params = PoseidonParams::<Fr>::new(4, 8, 54);
+    let jubjub_params = JubJubBN256::new();
+
+    let mut group = c.benchmark_group("merkle_proof");
+    group.bench_function("LinkedList LC", |b| b.iter(|| merkle_proof::<LC<Fr>>(&poseidon_params)));
+    group.bench_function("Vec LC", |b| b.iter(|| merkle_proof::<VecLC<Fr>>(&poseidon_params)));
+    group.finish();
+
+    let mut group = c.benchmark_group("eddsaposeidon_verify");
+    group.bench_function("LinkedList LC", |b| {
+        b.iter(|| eddsa::<LC<Fr>>(&eddsa_poseidon_params, &jubjub_params))
+    });
+    group.bench_function("Vec LC", |b| {
+        b.iter(|| eddsa::<VecLC<Fr>>(&eddsa_poseidon_params, &jubjub_params))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_lc);
+criterion_main!(benches);
@@ -0,0 +1,22 @@
+// Groth16 for the circuits authored in circom
+
+use super::*;
+use crate::circuit::{circom::R1cs, cs::new_rcs};
+use super::prover::{try_prove_from_witness, Proof};
+
+pub use super::verifier::verify;
+
+pub fn setup<E: Engine>(r1cs: &R1cs<E::Fr>) -> Parameters<E> {
+    let rcs = new_rcs(r1cs.clone().into_build_cs());
+    super::setup::setup_cs(&rcs)
+}
+
+// witness contains values of all circom wires, as read from .wtns file
+pub fn prove<E: Engine>(
+    params: &Parameters<E>,
+    r1cs: &R1cs<E::Fr>,
+    witness: &[Num<E::Fr>],
+) -> Result<(Vec<Num<E::Fr>>, Proof<E>), FawkesError> {
+    let witness = r1cs.split_witness(witness)?;
+    try_prove_from_witness(params, &witness)
+}
@@ -0,0 +1,83 @@
+use bellman::SynthesisError;
+
+use super::ParametersError;
+use crate::{circuit::gates::UnsatisfiedGate, ff_uint::PrimeField};
+
+#[derive(Debug)]
+pub enum FawkesError {
+    Io(std::io::Error),
+    Synthesis(SynthesisError),
+    AssignmentMissing,
+    InvalidPoint(String),
+    // circuit used other number of cached constants than recorded at setup
+    ConstTrackerMismatch { used: usize, total: usize },
+    WrongNumInputs { expected: usize, found: usize },
+    WrongNumAux { expected: usize, found: usize },
+    Parameters(ParametersError),
+    // the witness does not satisfy the gate, the description contains the values of the gate
+    Unsatisfied { index: usize, scope: Option<String>, description: String },
+}
+
+impl std::fmt::Display for FawkesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Synthesis(e) => write!(f, "synthesis error: {}", e),
+            Self::AssignmentMissing => write!(f, "assignment missing"),
+            Self::InvalidPoint(e) => write!(f, "invalid point: {}", e),
+            Self::ConstTrackerMismatch { used, total } => {
+                write!(f, "not all cached data used: used {} of {}", used, total)
+            }
+            Self::WrongNumInputs { expected, found } => {
+                write!(f, "wrong number of inputs: expected {}, found {}", expected, found)
+            }
+            Self::WrongNumAux { expected, found } => {
+                write!(f, "wrong number of aux values: expected {}, found {}", expected, found)
+            }
+            Self::Parameters(e) => write!(f, "{}", e),
+            Self::Unsatisfied { description, .. } => write!(f, "{}", description),
+        }
+    }
+}
+
+impl std::error::Error for FawkesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Synthesis(e) => Some(e),
+            Self::Parameters(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for FawkesError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<SynthesisError> for FawkesError {
+    fn from(e: SynthesisError) -> Self {
+        match e {
+            SynthesisError::AssignmentMissing => Self::AssignmentMissing,
+            e => Self::Synthesis(e),
+        }
+    }
+}
+
+impl From<ParametersError> for FawkesError {
+    fn from(e: ParametersError) -> Self {
+        Self::Parameters(e)
+    }
+}
+
+impl<Fr: PrimeField> From<UnsatisfiedGate<Fr>> for FawkesError {
+    fn from(e: UnsatisfiedGate<Fr>) -> Self {
+        Self::Unsatisfied {
+            index: e.index,
+            description: e.to_string(),
+            scope: e.scope,
+        }
+    }
+}
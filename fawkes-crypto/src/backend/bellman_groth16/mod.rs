@@ -1,12 +1,15 @@
 use crate::{
     circuit::{
         cs::{RCS, WitnessCS, CS},
-        lc::{Index}, gates::{GateSource, Gate, GateIterator, evaluate_gates_memory_size}
+        lc::{Index}, gates::{GateSource, Gate, GateIterator, evaluate_gates_memory_size},
+        optimizer::map_aux,
     },
-    core::signal::Signal,
-    ff_uint::{Num, PrimeField},
+    core::signal::{PublicSignal, Signal},
+    ff_uint::{Num, PrimeField, PrimeFieldParams, Uint},
 };
 
+use blake2_rfc::blake2s::Blake2s;
+
 use bit_vec::BitVec;
 
 
@@ -19,6 +22,7 @@ use borsh::{BorshSerialize, BorshDeserialize};
 use bellman::pairing::CurveAffine;
 use std::marker::PhantomData;
 use engines::Engine;
+pub use error::FawkesError;
 
 pub mod engines;
 #[cfg(feature = "rand_support")]
@@ -28,6 +32,9 @@ pub mod prover;
 pub mod setup;
 pub mod verifier;
 pub mod group;
+pub mod error;
+#[cfg(feature = "rand_support")]
+pub mod circom;
 
 
 
@@ -76,7 +83,7 @@ impl<E: Engine, C:CS<Fr=E::Fr>> bellman::Circuit<E::BE> for BellmanCS<E, C> {
             let v = bellman_cs.alloc_input(
                 || format!("input_{}", i),
                 || cs.get_value(Index::Input(i)).map(num_to_bellman_fp).ok_or(SynthesisError::AssignmentMissing)
-            ).unwrap();
+            )?;
             variables_input.push(v);
         }
 
@@ -84,12 +91,25 @@ impl<E: Engine, C:CS<Fr=E::Fr>> bellman::Circuit<E::BE> for BellmanCS<E, C> {
             let v = bellman_cs.alloc(
                 || format!("aux_{}", i),
                 || cs.get_value(Index::Aux(i)).map(num_to_bellman_fp).ok_or(SynthesisError::AssignmentMissing)
-            ).unwrap();
+            )?;
             variables_aux.push(v);
         }
 
-        
+        // bellman does not accept '/' in the names, so the scopes become nested namespaces
+        let mut namespaces: Vec<&str> = vec![];
         for (i,g) in cs.get_gate_iterator().enumerate() {
+            let scope = cs.get_gate_scope(i).unwrap_or("");
+            let path = scope.split('/').filter(|s| !s.is_empty()).collect::<Vec<_>>();
+            let common = namespaces.iter().zip(path.iter()).take_while(|(a, b)| a == b).count();
+            for _ in common..namespaces.len() {
+                bellman_cs.pop_namespace();
+            }
+            namespaces.truncate(common);
+            for name in path[common..].iter() {
+                bellman_cs.push_namespace(|| *name);
+                namespaces.push(name);
+            }
+
             bellman_cs.enforce(
                 || format!("constraint {}", i),
                 |_| convert_lc::<E>(&g.0, &variables_input, &variables_aux),
@@ -97,6 +117,9 @@ impl<E: Engine, C:CS<Fr=E::Fr>> bellman::Circuit<E::BE> for BellmanCS<E, C> {
                 |_| convert_lc::<E>(&g.2, &variables_input, &variables_aux),
             );
         }
+        for _ in 0..namespaces.len() {
+            bellman_cs.pop_namespace();
+        }
         Ok(())
     }
 }
@@ -136,7 +159,22 @@ pub fn bellman_fp_to_num<Fx: PrimeField, Fy: bellman::pairing::ff::PrimeField>(
     to
 }
 
-pub struct Parameters<E: Engine>(pub bellman::groth16::Parameters<E::BE>, pub u32, pub Vec<u8>, pub BitVec);
+// The last field is the map from aux indices of the optimized gates to aux indices of the circuit, see
+// circuit::optimizer
+pub struct Parameters<E: Engine>(
+    pub bellman::groth16::Parameters<E::BE>,
+    pub u32,
+    pub Vec<u8>,
+    pub BitVec,
+    pub Option<AuxMap>,
+);
+
+// num_aux is the number of aux variables of the circuit before the optimization
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct AuxMap {
+    pub num_aux: u32,
+    pub map: Vec<u32>,
+}
 
 impl<E: Engine> Parameters<E> {
     pub fn get_vk(&self) -> verifier::VK<E> {
@@ -151,31 +189,130 @@ impl<E: Engine> Parameters<E> {
         WitnessCS::rc_new(self.1 as usize, GateSource::Precomputed(&data.gates), &self.3)
     }
 
+    // aux values of the circuit mapped to the aux variables of the gates
+    pub fn map_aux(&self, values_aux: Vec<Num<E::Fr>>) -> Vec<Num<E::Fr>> {
+        match &self.4 {
+            Some(aux_map) => map_aux(&values_aux, &aux_map.map),
+            None => values_aux,
+        }
+    }
+
     pub fn write<W:std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(PARAMETERS_MAGIC)?;
+        BorshSerialize::serialize(&PARAMETERS_VERSION, writer)?;
+        BorshSerialize::serialize(&E::ID, writer)?;
+        BorshSerialize::serialize(&modulus_bytes::<E::Fq>(), writer)?;
+        BorshSerialize::serialize(&modulus_bytes::<E::Fr>(), writer)?;
+        BorshSerialize::serialize(&self.1, writer)?;
+        BorshSerialize::serialize(&(self.0.vk.ic.len() as u32), writer)?;
+        BorshSerialize::serialize(&params_hash(&self.2, &self.4), writer)?;
+        BorshSerialize::serialize(&self.4, writer)?;
+        self.write_body(writer)
+    }
+
+    // Layout without the header. It has no place for the aux map, so the optimized parameters could not be written.
+    pub fn write_legacy<W:std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if self.4.is_some() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "aux map is not supported by the legacy layout",
+            ));
+        }
+        self.write_body(writer)
+    }
+
+    fn write_body<W:std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         let bitvec_len = self.3.len() as u32;
         BorshSerialize::serialize(&self.1, writer)?;
         BorshSerialize::serialize(&self.2, writer)?;
         BorshSerialize::serialize(&bitvec_len, writer)?;
         BorshSerialize::serialize(&self.3.to_bytes(), writer)?;
-        self.0.write(writer)
+        self.0.write(&mut *writer)
     }
 
+    // Both layouts with and without the header are accepted
     pub fn read(reader: &mut &[u8], disallow_points_at_infinity: bool, checked: bool) -> std::io::Result<Self> {
+        Ok(Self::try_read(reader, disallow_points_at_infinity, checked)?)
+    }
+
+    // Same as read, but with typed errors. With checked flag the gates are decompressed to check their number.
+    pub fn try_read(reader: &mut &[u8], disallow_points_at_infinity: bool, checked: bool) -> Result<Self, ParametersError> {
+        if !reader.starts_with(PARAMETERS_MAGIC) {
+            return Self::read_legacy(reader, disallow_points_at_infinity, checked);
+        }
+        *reader = &reader[PARAMETERS_MAGIC.len()..];
+
+        let version: u32 = BorshDeserialize::deserialize(reader)?;
+        if version != PARAMETERS_VERSION {
+            return Err(ParametersError::UnsupportedVersion(version));
+        }
+
+        let engine_id: u32 = BorshDeserialize::deserialize(reader)?;
+        if engine_id != E::ID {
+            return Err(ParametersError::WrongEngine { expected: E::ID, found: engine_id });
+        }
+
+        let fq: Vec<u8> = BorshDeserialize::deserialize(reader)?;
+        let fr: Vec<u8> = BorshDeserialize::deserialize(reader)?;
+        if fq != modulus_bytes::<E::Fq>() || fr != modulus_bytes::<E::Fr>() {
+            return Err(ParametersError::WrongModulus);
+        }
+
+        let num_gates: u32 = BorshDeserialize::deserialize(reader)?;
+        let num_input: u32 = BorshDeserialize::deserialize(reader)?;
+        let hash: [u8; 32] = BorshDeserialize::deserialize(reader)?;
+        let aux_map: Option<AuxMap> = BorshDeserialize::deserialize(reader)?;
+
+        let mut res = Self::read_legacy(reader, disallow_points_at_infinity, checked)?;
+
+        if params_hash(&res.2, &aux_map) != hash {
+            return Err(ParametersError::WrongHash);
+        }
+        if res.1 != num_gates {
+            return Err(ParametersError::WrongNumGates { expected: num_gates, found: res.1 });
+        }
+        if checked {
+            let found = GateIterator::new(&GateSource::<E::Fr>::Compressed(&res.2)).count() as u32;
+            if found != num_gates {
+                return Err(ParametersError::WrongNumGates { expected: num_gates, found });
+            }
+        }
+        let found = res.0.vk.ic.len() as u32;
+        if found != num_input {
+            return Err(ParametersError::WrongNumInputs { expected: num_input, found });
+        }
+
+        if let Some(aux_map) = &aux_map {
+            let expected = res.0.l.len() as u32;
+            let found = aux_map.map.len() as u32;
+            if found != expected {
+                return Err(ParametersError::WrongAuxMapLen { expected, found });
+            }
+            if let Some(&index) = aux_map.map.iter().find(|&&i| i >= aux_map.num_aux) {
+                return Err(ParametersError::AuxIndexOverflow { index, num_aux: aux_map.num_aux });
+            }
+        }
+        res.4 = aux_map;
+
+        Ok(res)
+    }
+
+    pub fn read_legacy(reader: &mut &[u8], disallow_points_at_infinity: bool, checked: bool) -> Result<Self, ParametersError> {
         let e1 = BorshDeserialize::deserialize(reader)?;
         let e2 = BorshDeserialize::deserialize(reader)?;
         let e3_len = <u32 as BorshDeserialize>::deserialize(reader)? as usize;
         let e3_buf:Vec<u8> = BorshDeserialize::deserialize(reader)?;
 
         if e3_len > e3_buf.len() * 8 {
-            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "inconsistent bitvec length"));
+            return Err(ParametersError::InconsistentBitVec);
         }
 
         let mut e3 = BitVec::from_bytes(&e3_buf);
         e3.truncate(e3_len);
 
 
-        let e0 = bellman::groth16::Parameters::read(reader, disallow_points_at_infinity, checked)?;
-        Ok(Self(e0, e1, e2, e3))
+        let e0 = bellman::groth16::Parameters::read(&mut *reader, disallow_points_at_infinity, checked)?;
+        Ok(Self(e0, e1, e2, e3, None))
     }
 
     pub fn precompute(&self) -> PrecomputedData<E::Fr> {
@@ -189,6 +326,87 @@ impl<E: Engine> Parameters<E> {
     }
 }
 
+pub const PARAMETERS_MAGIC: &[u8; 4] = b"FWKP";
+pub const PARAMETERS_VERSION: u32 = 1;
+
+fn modulus_bytes<Fx: PrimeField>() -> Vec<u8> {
+    <Fx as PrimeFieldParams>::MODULUS.to_little_endian()
+}
+
+fn params_hash(gates: &[u8], aux_map: &Option<AuxMap>) -> [u8; 32] {
+    let mut h = Blake2s::new(32);
+    h.update(gates);
+    h.update(&aux_map.try_to_vec().unwrap());
+    let mut res = [0; 32];
+    res.copy_from_slice(h.finalize().as_ref());
+    res
+}
+
+#[derive(Debug)]
+pub enum ParametersError {
+    Io(std::io::Error),
+    UnsupportedVersion(u32),
+    WrongEngine { expected: u32, found: u32 },
+    WrongModulus,
+    WrongHash,
+    WrongNumGates { expected: u32, found: u32 },
+    WrongNumInputs { expected: u32, found: u32 },
+    InconsistentBitVec,
+    WrongAuxMapLen { expected: u32, found: u32 },
+    AuxIndexOverflow { index: u32, num_aux: u32 },
+}
+
+impl std::fmt::Display for ParametersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported parameters version {}", v),
+            Self::WrongEngine { expected, found } => {
+                write!(f, "wrong engine id: expected {}, found {}", expected, found)
+            }
+            Self::WrongModulus => write!(f, "wrong field modulus"),
+            Self::WrongHash => write!(f, "gates and aux map hash mismatch"),
+            Self::WrongNumGates { expected, found } => {
+                write!(f, "wrong number of gates: expected {}, found {}", expected, found)
+            }
+            Self::WrongNumInputs { expected, found } => {
+                write!(f, "wrong number of inputs: expected {}, found {}", expected, found)
+            }
+            Self::InconsistentBitVec => write!(f, "inconsistent bitvec length"),
+            Self::WrongAuxMapLen { expected, found } => {
+                write!(f, "wrong aux map length: expected {}, found {}", expected, found)
+            }
+            Self::AuxIndexOverflow { index, num_aux } => {
+                write!(f, "aux map index {} overflows {} aux variables", index, num_aux)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParametersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ParametersError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ParametersError> for std::io::Error {
+    fn from(e: ParametersError) -> Self {
+        match e {
+            ParametersError::Io(e) => e,
+            e => std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()),
+        }
+    }
+}
+
 pub struct PrecomputedData<Fr: PrimeField> {
     gates: Vec<Gate<Fr>>
 }
@@ -2,6 +2,7 @@
 use super::osrng::OsRng;
 use super::*;
 use super::group::{G1Point, G2Point};
+use crate::circuit::{gates::check_gates, witness::Witness};
 
 #[cfg(feature = "serde_support")]
 use serde::{Serialize, Deserialize};
@@ -18,11 +19,15 @@ pub struct Proof<E: Engine> {
 
 impl<E: Engine> Proof<E> {
     pub fn to_bellman(&self) -> bellman::groth16::Proof<E::BE> {
-        bellman::groth16::Proof {
-            a: self.a.to_bellman(),
-            b: self.b.to_bellman(),
-            c: self.c.to_bellman(),
-        }
+        self.try_to_bellman().unwrap()
+    }
+
+    pub fn try_to_bellman(&self) -> Result<bellman::groth16::Proof<E::BE>, FawkesError> {
+        Ok(bellman::groth16::Proof {
+            a: self.a.try_to_bellman()?,
+            b: self.b.try_to_bellman()?,
+            c: self.c.try_to_bellman()?,
+        })
     }
 
     pub fn from_bellman(proof: &bellman::groth16::Proof<E::BE>) -> Self {
@@ -66,8 +71,19 @@ pub fn prove<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec: Signal<Witne
     input_sec: &Sec::Value,
     circuit: C,
 ) -> (Vec<Num<E::Fr>>, Proof<E>) {
+    try_prove(params, input_pub, input_sec, circuit).unwrap()
+}
+
+#[cfg(feature = "rand_support")]
+pub fn try_prove<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec: Signal<WitnessCS<'a, E::Fr>>, C: Fn(Pub, Sec)>(
+    params: &'a Parameters<E>,
+    input_pub: &Pub::Value,
+    input_sec: &Sec::Value,
+    circuit: C,
+) -> Result<(Vec<Num<E::Fr>>, Proof<E>), FawkesError> {
     let rcs = &params.get_witness_rcs();
-    prove_internal(params, input_pub, input_sec, circuit, rcs)
+    synthesize_witness(params, input_pub, input_sec, circuit, rcs)?;
+    try_prove_cs(params, rcs)
 }
 
 #[cfg(feature = "rand_support")]
@@ -78,6 +94,72 @@ pub fn prove_precomputed<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec:
     circuit: C,
     precomputed: &'a PrecomputedData<E::Fr>,
 ) -> (Vec<Num<E::Fr>>, Proof<E>) {
+    try_prove_precomputed(params, input_pub, input_sec, circuit, precomputed).unwrap()
+}
+
+#[cfg(feature = "rand_support")]
+pub fn try_prove_precomputed<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec: Signal<WitnessCS<'a, E::Fr>>, C: Fn(Pub, Sec)>(
+    params: &'a Parameters<E>,
+    input_pub: &Pub::Value,
+    input_sec: &Sec::Value,
+    circuit: C,
+    precomputed: &'a PrecomputedData<E::Fr>,
+) -> Result<(Vec<Num<E::Fr>>, Proof<E>), FawkesError> {
+    let rcs = &params.get_witness_rcs_precomputed(precomputed);
+    synthesize_witness(params, input_pub, input_sec, circuit, rcs)?;
+    try_prove_cs(params, rcs)
+}
+
+// Same as prove, but with one input, which public part is marked with #[public]
+#[cfg(feature = "rand_support")]
+pub fn prove_single<'a, E: Engine, S: PublicSignal<WitnessCS<'a, E::Fr>>, C: Fn(S)>(
+    params: &'a Parameters<E>,
+    input: &S::Value,
+    circuit: C,
+) -> (Vec<Num<E::Fr>>, Proof<E>) {
+    try_prove_single(params, input, circuit).unwrap()
+}
+
+#[cfg(feature = "rand_support")]
+pub fn try_prove_single<'a, E: Engine, S: PublicSignal<WitnessCS<'a, E::Fr>>, C: Fn(S)>(
+    params: &'a Parameters<E>,
+    input: &S::Value,
+    circuit: C,
+) -> Result<(Vec<Num<E::Fr>>, Proof<E>), FawkesError> {
+    let rcs = &params.get_witness_rcs();
+    let signal = S::alloc(rcs, Some(input));
+    signal.inputize_public();
+
+    circuit(signal);
+
+    let mut cs = rcs.borrow_mut();
+    check_const_tracker(&cs)?;
+    cs.values_aux = params.map_aux(std::mem::take(&mut cs.values_aux));
+    drop(cs);
+
+    try_prove_cs(params, rcs)
+}
+
+// Check all gates against the witness before proving and return the first unsatisfied gate instead of an invalid proof
+#[cfg(feature = "rand_support")]
+pub fn prove_checked<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec: Signal<WitnessCS<'a, E::Fr>>, C: Fn(Pub, Sec)>(
+    params: &'a Parameters<E>,
+    input_pub: &Pub::Value,
+    input_sec: &Sec::Value,
+    circuit: C,
+) -> Result<(Vec<Num<E::Fr>>, Proof<E>), FawkesError> {
+    let rcs = &params.get_witness_rcs();
+    prove_internal(params, input_pub, input_sec, circuit, rcs)
+}
+
+#[cfg(feature = "rand_support")]
+pub fn prove_precomputed_checked<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec: Signal<WitnessCS<'a, E::Fr>>, C: Fn(Pub, Sec)>(
+    params: &'a Parameters<E>,
+    input_pub: &Pub::Value,
+    input_sec: &Sec::Value,
+    circuit: C,
+    precomputed: &'a PrecomputedData<E::Fr>,
+) -> Result<(Vec<Num<E::Fr>>, Proof<E>), FawkesError> {
     let rcs = &params.get_witness_rcs_precomputed(precomputed);
     prove_internal(params, input_pub, input_sec, circuit, rcs)
 }
@@ -88,26 +170,136 @@ fn prove_internal<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec: Signal<
     input_pub: &Pub::Value,
     input_sec: &Sec::Value,
     circuit: C,
-    rcs: &RCS<WitnessCS<'a, E::Fr>>
-) -> (Vec<Num<E::Fr>>, Proof<E>) {
+    rcs: &RCS<WitnessCS<'a, E::Fr>>,
+) -> Result<(Vec<Num<E::Fr>>, Proof<E>), FawkesError> {
+    // the gates of the optimized parameters do not match the gates of the circuit, so their scopes are unknown
+    if params.4.is_none() {
+        rcs.borrow_mut().record_scopes();
+    }
+    synthesize_witness(params, input_pub, input_sec, circuit, rcs)?;
+    {
+        let cs = rcs.borrow();
+        check_gates(cs.get_gate_iterator(), &cs.values_input, &cs.values_aux).map_err(|mut e| {
+            e.scope = cs.get_gate_scope(e.index).map(|s| s.to_string());
+            e
+        })?;
+    }
+    try_prove_cs(params, rcs)
+}
+
+// Run the circuit and put the witness of the (optimized) constraint system into rcs
+#[cfg(feature = "rand_support")]
+fn synthesize_witness<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec: Signal<WitnessCS<'a, E::Fr>>, C: Fn(Pub, Sec)>(
+    params: &'a Parameters<E>,
+    input_pub: &Pub::Value,
+    input_sec: &Sec::Value,
+    circuit: C,
+    rcs: &RCS<WitnessCS<'a, E::Fr>>,
+) -> Result<(), FawkesError> {
     let signal_pub = Pub::alloc(rcs, Some(input_pub));
     signal_pub.inputize();
     let signal_sec = Sec::alloc(rcs, Some(input_sec));
 
     circuit(signal_pub, signal_sec);
 
+    let mut cs = rcs.borrow_mut();
+    check_const_tracker(&cs)?;
+    cs.values_aux = params.map_aux(std::mem::take(&mut cs.values_aux));
+    Ok(())
+}
+
+fn check_const_tracker<Fr: PrimeField>(cs: &WitnessCS<Fr>) -> Result<(), FawkesError> {
+    if cs.const_tracker_index != cs.const_tracker.len() {
+        return Err(FawkesError::ConstTrackerMismatch {
+            used: cs.const_tracker_index,
+            total: cs.const_tracker.len(),
+        });
+    }
+    Ok(())
+}
+
+// Run the circuit without proving, so the witness could be proved later with prove_from_witness
+pub fn build_witness<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec: Signal<WitnessCS<'a, E::Fr>>, C: Fn(Pub, Sec)>(
+    params: &'a Parameters<E>,
+    input_pub: &Pub::Value,
+    input_sec: &Sec::Value,
+    circuit: C,
+) -> Witness<E::Fr> {
+    try_build_witness(params, input_pub, input_sec, circuit).unwrap()
+}
+
+pub fn try_build_witness<'a, E: Engine, Pub: Signal<WitnessCS<'a, E::Fr>>, Sec: Signal<WitnessCS<'a, E::Fr>>, C: Fn(Pub, Sec)>(
+    params: &'a Parameters<E>,
+    input_pub: &Pub::Value,
+    input_sec: &Sec::Value,
+    circuit: C,
+) -> Result<Witness<E::Fr>, FawkesError> {
+    let ref rcs = params.get_witness_rcs();
+    let signal_pub = Pub::alloc(rcs, Some(input_pub));
+    signal_pub.inputize();
+    let signal_sec = Sec::alloc(rcs, Some(input_sec));
+
+    circuit(signal_pub, signal_sec);
+
+    let cs = rcs.borrow();
+    check_const_tracker(&cs)?;
+    Ok(Witness::from_cs(&cs))
+}
+
+// witness is the assignment of the circuit variables, as returned by build_witness
+#[cfg(feature = "rand_support")]
+pub fn prove_from_witness<E: Engine>(
+    params: &Parameters<E>,
+    witness: &Witness<E::Fr>,
+) -> (Vec<Num<E::Fr>>, Proof<E>) {
+    try_prove_from_witness(params, witness).unwrap()
+}
+
+#[cfg(feature = "rand_support")]
+pub fn try_prove_from_witness<E: Engine>(
+    params: &Parameters<E>,
+    witness: &Witness<E::Fr>,
+) -> Result<(Vec<Num<E::Fr>>, Proof<E>), FawkesError> {
+    if witness.values_input.len() != params.0.vk.ic.len() {
+        return Err(FawkesError::WrongNumInputs {
+            expected: params.0.vk.ic.len(),
+            found: witness.values_input.len(),
+        });
+    }
+    // the witness of the circuit is mapped to the optimized gates, so it has the size of the circuit before the optimization
+    let found = witness.values_aux.len();
+    let expected = match &params.4 {
+        Some(aux_map) => aux_map.num_aux as usize,
+        None => params.0.l.len(),
+    };
+    if found != expected {
+        return Err(FawkesError::WrongNumAux { expected, found });
+    }
+    let rcs = params.get_witness_rcs();
+    {
+        let mut cs = rcs.borrow_mut();
+        cs.values_input = witness.values_input.clone();
+        cs.values_aux = params.map_aux(witness.values_aux.clone());
+    }
+    try_prove_cs(params, &rcs)
+}
+
+#[cfg(feature = "rand_support")]
+fn try_prove_cs<'a, E: Engine>(
+    params: &'a Parameters<E>,
+    rcs: &RCS<WitnessCS<'a, E::Fr>>,
+) -> Result<(Vec<Num<E::Fr>>, Proof<E>), FawkesError> {
     let bcs = BellmanCS::<E, WitnessCS<E::Fr>>::new(rcs.clone());
 
     let ref mut rng = OsRng::new();
     let proof =
-        Proof::from_bellman(&bellman::groth16::create_random_proof(bcs, &params.0, rng).unwrap());
+        Proof::from_bellman(&bellman::groth16::create_random_proof(bcs, &params.0, rng)?);
 
     let cs = rcs.borrow();
-    assert!(cs.const_tracker_index==cs.const_tracker.len(), "not all cached data used");
     let mut inputs = Vec::with_capacity(cs.num_input());
     for i in 1..cs.num_input() as u32{
-        inputs.push(cs.get_value(Index::Input(i)).unwrap())
+        inputs.push(cs.get_value(Index::Input(i)).ok_or(FawkesError::AssignmentMissing)?)
     }
     
-    (inputs, proof)
+    Ok((inputs, proof))
 }
@@ -2,11 +2,17 @@ use std::io::Write;
 
 use super::osrng::OsRng;
 use super::*;
-use crate::circuit::cs::BuildCS;
+use crate::circuit::{cs::{new_rcs, BuildCS}, lc::AbstractLC, optimizer::optimize};
 
 pub fn setup<E: Engine, Pub: Signal<BuildCS<E::Fr>>, Sec: Signal<BuildCS<E::Fr>>, C: Fn(Pub, Sec)>(
     circuit: C,
 ) -> Parameters<E> {
+    try_setup(circuit).unwrap()
+}
+
+pub fn try_setup<E: Engine, Pub: Signal<BuildCS<E::Fr>>, Sec: Signal<BuildCS<E::Fr>>, C: Fn(Pub, Sec)>(
+    circuit: C,
+) -> Result<Parameters<E>, FawkesError> {
     let ref rcs = BuildCS::rc_new();
     let signal_pub = Pub::alloc(rcs, None);
     signal_pub.inputize();
@@ -14,10 +20,60 @@ pub fn setup<E: Engine, Pub: Signal<BuildCS<E::Fr>>, Sec: Signal<BuildCS<E::Fr>>
 
     circuit(signal_pub, signal_sec);
 
-    let bcs = BellmanCS::<E, BuildCS<E::Fr>>::new(rcs.clone());
+    try_setup_cs(rcs)
+}
+
+// Same as setup, but with one input, which public part is marked with #[public]
+pub fn setup_single<E: Engine, S: PublicSignal<BuildCS<E::Fr>>, C: Fn(S)>(circuit: C) -> Parameters<E> {
+    try_setup_single(circuit).unwrap()
+}
+
+pub fn try_setup_single<E: Engine, S: PublicSignal<BuildCS<E::Fr>>, C: Fn(S)>(
+    circuit: C,
+) -> Result<Parameters<E>, FawkesError> {
+    let ref rcs = BuildCS::rc_new();
+    let signal = S::alloc(rcs, None);
+    signal.inputize_public();
+
+    circuit(signal);
+
+    try_setup_cs(rcs)
+}
+
+// Same as setup, but the gates are optimized before the generation of the parameters
+pub fn setup_optimized<E: Engine, Pub: Signal<BuildCS<E::Fr>>, Sec: Signal<BuildCS<E::Fr>>, C: Fn(Pub, Sec)>(
+    circuit: C,
+) -> Parameters<E> {
+    try_setup_optimized(circuit).unwrap()
+}
+
+pub fn try_setup_optimized<E: Engine, Pub: Signal<BuildCS<E::Fr>>, Sec: Signal<BuildCS<E::Fr>>, C: Fn(Pub, Sec)>(
+    circuit: C,
+) -> Result<Parameters<E>, FawkesError> {
+    let ref rcs = BuildCS::rc_new();
+    let signal_pub = Pub::alloc(rcs, None);
+    signal_pub.inputize();
+    let signal_sec = Sec::alloc(rcs, None);
+
+    circuit(signal_pub, signal_sec);
+
+    let cs = rcs.borrow();
+    let (opt, map) = optimize(&cs);
+    let mut params = try_setup_cs(&new_rcs(opt))?;
+    params.4 = Some(AuxMap { num_aux: cs.num_aux as u32, map });
+    Ok(params)
+}
+
+// Generate parameters for already built constraint system
+pub fn setup_cs<E: Engine, L: AbstractLC<E::Fr>>(rcs: &RCS<BuildCS<E::Fr, L>>) -> Parameters<E> {
+    try_setup_cs(rcs).unwrap()
+}
+
+pub fn try_setup_cs<E: Engine, L: AbstractLC<E::Fr>>(rcs: &RCS<BuildCS<E::Fr, L>>) -> Result<Parameters<E>, FawkesError> {
+    let bcs = BellmanCS::<E, BuildCS<E::Fr, L>>::new(rcs.clone());
 
     let ref mut rng = OsRng::new();
-    let bp = bellman::groth16::generate_random_parameters(bcs, rng).unwrap();
+    let bp = bellman::groth16::generate_random_parameters(bcs, rng)?;
     let cs=rcs.borrow();
 
     let num_gates = cs.gates.len();
@@ -25,11 +81,11 @@ pub fn setup<E: Engine, Pub: Signal<BuildCS<E::Fr>>, Sec: Signal<BuildCS<E::Fr>>
     let mut buf = std::io::Cursor::new(vec![]);
     let mut c = brotli::CompressorWriter::new(&mut buf, 4096, 9, 22);
     for g in cs.gates.iter() {
-        c.write_all(&g.try_to_vec().unwrap()).unwrap();
+        c.write_all(&g.try_to_vec()?)?;
     }
 
-    c.flush().unwrap();
+    c.flush()?;
     drop(c);
 
-    Parameters(bp, num_gates as u32, buf.into_inner(), cs.const_tracker.clone())
+    Ok(Parameters(bp, num_gates as u32, buf.into_inner(), cs.const_tracker.clone(), None))
 }
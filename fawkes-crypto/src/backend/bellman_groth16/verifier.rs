@@ -20,15 +20,19 @@ pub struct VK<E: Engine> {
 impl<E: Engine> VK<E> {
     // fill missing fields with zeroes
     pub fn to_bellman(&self) -> bellman::groth16::VerifyingKey<E::BE> {
-        bellman::groth16::VerifyingKey {
-            alpha_g1: self.alpha.to_bellman(),
+        self.try_to_bellman().unwrap()
+    }
+
+    pub fn try_to_bellman(&self) -> Result<bellman::groth16::VerifyingKey<E::BE>, FawkesError> {
+        Ok(bellman::groth16::VerifyingKey {
+            alpha_g1: self.alpha.try_to_bellman()?,
             beta_g1: <E::BE as bellman::pairing::Engine>::G1Affine::zero(),
-            beta_g2: self.beta.to_bellman(),
-            gamma_g2: self.gamma.to_bellman(),
+            beta_g2: self.beta.try_to_bellman()?,
+            gamma_g2: self.gamma.try_to_bellman()?,
             delta_g1: <E::BE as bellman::pairing::Engine>::G1Affine::zero(),
-            delta_g2: self.delta.to_bellman(),
-            ic: self.ic.iter().map(|e| e.to_bellman()).collect(),
-        }
+            delta_g2: self.delta.try_to_bellman()?,
+            ic: self.ic.iter().map(|e| e.try_to_bellman()).collect::<Result<_, _>>()?,
+        })
     }
 
     pub fn from_bellman(vk: &bellman::groth16::VerifyingKey<E::BE>) -> Self {
@@ -73,9 +77,26 @@ impl<E: Engine> BorshDeserialize for VK<E> {
 }
 
 pub fn verify<E: Engine>(vk: &VK<E>, proof: &Proof<E>, inputs: &[Num<E::Fr>]) -> bool {
+    try_verify(vk, proof, inputs).unwrap()
+}
+
+// Malformed proof or verifying key is an error, not a panic
+pub fn try_verify<E: Engine>(vk: &VK<E>, proof: &Proof<E>, inputs: &[Num<E::Fr>]) -> Result<bool, FawkesError> {
+    if inputs.len() + 1 != vk.ic.len() {
+        return Err(FawkesError::WrongNumInputs { expected: vk.ic.len().saturating_sub(1), found: inputs.len() });
+    }
     let inputs: Vec<_> = inputs.iter().map(|e| num_to_bellman_fp(*e)).collect();
-    let vk = vk.to_bellman();
-    let proof = proof.to_bellman();
+    let vk = vk.try_to_bellman()?;
+    let proof = proof.try_to_bellman()?;
     let pvk = bellman::groth16::prepare_verifying_key(&vk);
-    bellman::groth16::verify_proof(&pvk, &proof, &inputs).unwrap()
+    Ok(bellman::groth16::verify_proof(&pvk, &proof, &inputs)?)
+}
+
+// Public inputs for the public part of the circuit input, in the same order as inputize_public makes them.
+// The secret fields are not needed, the public part of the full value is returned by PublicSignal::public_value.
+// Any constraint system could be used for C, the inputs do not depend on it.
+pub fn public_inputs<C: CS, S: PublicSignal<C>>(value: &S::PublicValue) -> Vec<Num<C::Fr>> {
+    let mut inputs = vec![];
+    S::write_public_inputs(value, &mut inputs);
+    inputs
 }
@@ -1,12 +1,13 @@
 use ff_uint::NumRepr;
 
 use crate::{
-    circuit::{bool::CBool, num::CNum, cs::CS},
+    circuit::{bool::CBool, num::CNum, cs::CS, scope::Scope},
     core::signal::Signal,
-    ff_uint::{BitIterLE, Num, PrimeFieldParams},
+    ff_uint::{BitIterLE, Num, PrimeField, PrimeFieldParams, Uint},
 };
 
 pub fn c_into_bits_le<C: CS>(signal: &CNum<C>, limit: usize) -> Vec<CBool<C>> {
+    let _scope = Scope::new(signal.get_cs(), "into_bits_le");
     match signal.as_const() {
         Some(value) => {
             let mut bits = Vec::<CBool<C>>::new();
@@ -24,6 +25,11 @@ pub fn c_into_bits_le<C: CS>(signal: &CNum<C>, limit: usize) -> Vec<CBool<C>> {
             assert!(remained_value.is_zero());
             bits
         }
+        // no bits, so the signal is zero
+        _ if limit == 0 => {
+            signal.assert_zero();
+            vec![]
+        }
         _ => {
             let value = signal.get_value();
             let mut remained_signal = signal.clone();
@@ -49,19 +55,129 @@ pub fn c_into_bits_le<C: CS>(signal: &CNum<C>, limit: usize) -> Vec<CBool<C>> {
 
 // return true if s1 > s2
 // assuming log2(s1) <= limit, log2(s2) <= limit
-// TODO: optimize for constant cases
 pub fn c_comp<C: CS>(s1:&CNum<C>, s2:&CNum<C>, limit:usize) -> CBool<C> {
+    if let (Some(a), Some(b)) = (s1.as_const(), s2.as_const()) {
+        return s1.derive_const(&(a.to_uint() > b.to_uint()));
+    }
+    let _scope = Scope::new(s1.get_cs(), "comp");
     let t = (NumRepr::ONE << (limit as u32)) - NumRepr::ONE;
     let t = Num::from_uint(t).unwrap();
     let n = t + s1 - s2;
     c_into_bits_le(&n, limit+1)[limit].clone()
 }
 
+// All the comparators below assume log2(s1) <= limit, log2(s2) <= limit.
+// The values are not range checked, use c_assert_range if they are not trusted.
+
+// return true if s1 < s2
+pub fn c_lt<C: CS>(s1:&CNum<C>, s2:&CNum<C>, limit:usize) -> CBool<C> {
+    c_comp(s2, s1, limit)
+}
+
+// return true if s1 <= s2
+pub fn c_le<C: CS>(s1:&CNum<C>, s2:&CNum<C>, limit:usize) -> CBool<C> {
+    !c_comp(s1, s2, limit)
+}
+
+// return true if s1 > s2
+pub fn c_gt<C: CS>(s1:&CNum<C>, s2:&CNum<C>, limit:usize) -> CBool<C> {
+    c_comp(s1, s2, limit)
+}
+
+// return true if s1 >= s2
+pub fn c_ge<C: CS>(s1:&CNum<C>, s2:&CNum<C>, limit:usize) -> CBool<C> {
+    !c_comp(s2, s1, limit)
+}
+
+fn max_value<Fr: PrimeField>(limit: usize) -> NumRepr<Fr::Inner> {
+    (NumRepr::ONE << (limit as u32)) - NumRepr::ONE
+}
+
+// return true if signal < ct
+// the result is constant, if ct is 0 or out of the limit
+pub fn c_lt_constant<C: CS>(signal:&CNum<C>, ct:Num<C::Fr>, limit:usize) -> CBool<C> {
+    let ct = ct.to_uint();
+    if ct.is_zero() {
+        signal.derive_const(&false)
+    } else if ct > max_value::<C::Fr>(limit) {
+        signal.derive_const(&true)
+    } else {
+        c_comp(&signal.derive_const(&Num::from_uint(ct).unwrap()), signal, limit)
+    }
+}
+
+// return true if signal > ct
+// the result is constant, if ct is out of the limit
+pub fn c_gt_constant<C: CS>(signal:&CNum<C>, ct:Num<C::Fr>, limit:usize) -> CBool<C> {
+    let ct = ct.to_uint();
+    if ct >= max_value::<C::Fr>(limit) {
+        signal.derive_const(&false)
+    } else {
+        c_comp(signal, &signal.derive_const(&Num::from_uint(ct).unwrap()), limit)
+    }
+}
+
+// return true if signal <= ct
+pub fn c_le_constant<C: CS>(signal:&CNum<C>, ct:Num<C::Fr>, limit:usize) -> CBool<C> {
+    !c_gt_constant(signal, ct, limit)
+}
+
+// return true if signal >= ct
+pub fn c_ge_constant<C: CS>(signal:&CNum<C>, ct:Num<C::Fr>, limit:usize) -> CBool<C> {
+    !c_lt_constant(signal, ct, limit)
+}
+
+// return the smaller of s1 and s2
+pub fn c_min<C: CS>(s1:&CNum<C>, s2:&CNum<C>, limit:usize) -> CNum<C> {
+    s1.switch(&c_gt(s2, s1, limit), s2)
+}
+
+// return the larger of s1 and s2
+pub fn c_max<C: CS>(s1:&CNum<C>, s2:&CNum<C>, limit:usize) -> CNum<C> {
+    s2.switch(&c_gt(s2, s1, limit), s1)
+}
+
+// enforce log2(signal) <= bits
+pub fn c_assert_range<C: CS>(signal:&CNum<C>, bits:usize) {
+    match signal.as_const() {
+        Some(value) => assert!((value.to_uint() >> (bits as u32)).is_zero(), "constant is out of range"),
+        _ => {
+            let _scope = Scope::new(signal.get_cs(), "assert_range");
+            c_into_bits_le(signal, bits);
+        }
+    }
+}
+
+// enforce lo <= signal <= hi
+pub fn c_assert_in_interval<C: CS>(signal:&CNum<C>, lo:Num<C::Fr>, hi:Num<C::Fr>) {
+    let (lo_uint, hi_uint) = (lo.to_uint(), hi.to_uint());
+    assert!(lo_uint <= hi_uint, "empty interval");
+    match signal.as_const() {
+        Some(value) => {
+            let value = value.to_uint();
+            assert!(lo_uint <= value && value <= hi_uint, "constant is out of interval");
+        }
+        _ => {
+            let _scope = Scope::new(signal.get_cs(), "assert_in_interval");
+            let width = hi_uint - lo_uint;
+            let bits = width.0.bits();
+            assert!(bits + 1 < Num::<C::Fr>::MODULUS_BITS as usize, "interval is too large");
+            // signal - lo < 2^bits and hi - signal < 2^bits, so the checks do not wrap the field
+            c_into_bits_le(&(signal - lo), bits);
+            // the upper bound is implied, if the interval width is 2^bits - 1
+            if width != max_value::<C::Fr>(bits) {
+                c_into_bits_le(&(hi - signal), bits);
+            }
+        }
+    }
+}
+
 // return true if signal > ct
 // assuming at least one bit in signal
 pub fn c_comp_constant<C: CS>(signal: &[CBool<C>], ct: Num<C::Fr>) -> CBool<C> {
     let siglen = signal.len();
     assert!(siglen > 0, "should be at least one input signal");
+    let _scope = Scope::new(signal[0].get_cs(), "comp_constant");
     let c_false = signal[0].derive_const(&false);
     if (ct.to_uint() >> (siglen as u32)).is_zero() {
         let cs = signal[0].get_cs();
@@ -105,6 +221,7 @@ pub fn c_comp_constant<C: CS>(signal: &[CBool<C>], ct: Num<C::Fr>) -> CBool<C> {
 }
 
 pub fn c_into_bits_le_strict<C: CS>(signal: &CNum<C>) -> Vec<CBool<C>> {
+    let _scope = Scope::new(signal.get_cs(), "into_bits_le_strict");
     let bits = c_into_bits_le(signal, C::Fr::MODULUS_BITS as usize);
     let cmp_res = c_comp_constant(&bits, -Num::ONE);
     cmp_res.assert_const(&false);
@@ -121,3 +238,19 @@ pub fn c_from_bits_le<C: CS>(bits: &[CBool<C>]) -> CNum<C> {
     }
     acc
 }
+
+// Range check the bytes and return their bits, the most significant bit of each byte goes first
+pub fn c_bytes_to_bits_be<C: CS>(bytes: &[CNum<C>]) -> Vec<CBool<C>> {
+    bytes
+        .iter()
+        .flat_map(|b| c_into_bits_le(b, 8).into_iter().rev())
+        .collect()
+}
+
+// Inverse of c_bytes_to_bits_be, the number of bits should be a multiple of 8
+pub fn c_bits_be_to_bytes<C: CS>(bits: &[CBool<C>]) -> Vec<CNum<C>> {
+    assert_eq!(bits.len() % 8, 0, "number of bits should be a multiple of 8");
+    bits.chunks(8)
+        .map(|c| c_from_bits_le(&c.iter().rev().cloned().collect::<Vec<_>>()))
+        .collect()
+}
@@ -0,0 +1,77 @@
+use crate::{
+    circuit::{
+        bool::CBool,
+        cs::{CS, RCS},
+        scope::Scope,
+        word::{add_words, const_word, rotr, xor, Word},
+    },
+    core::signal::Signal,
+    native::{
+        blake2s::{initial_state, BLOCK_BYTES, SIGMA},
+        sha256::IV,
+    },
+};
+
+fn g<C: CS>(v: &mut [Word<C>], [a, b, c, d]: [usize; 4], x: &Word<C>, y: &Word<C>) {
+    v[a] = add_words(&[&v[a], &v[b], x]);
+    v[d] = rotr(&xor(&v[d], &v[a]), 16);
+    v[c] = add_words(&[&v[c], &v[d]]);
+    v[b] = rotr(&xor(&v[b], &v[c]), 12);
+    v[a] = add_words(&[&v[a], &v[b], y]);
+    v[d] = rotr(&xor(&v[d], &v[a]), 8);
+    v[c] = add_words(&[&v[c], &v[d]]);
+    v[b] = rotr(&xor(&v[b], &v[c]), 7);
+}
+
+// The counter and the final flag are known at the circuit building time, so they are constant
+fn compress<C: CS>(h: &[Word<C>], block: &[CBool<C>], counter: u64, last: bool) -> Vec<Word<C>> {
+    let s = &block[0];
+    let m = block.chunks(32).map(|c| c.to_vec()).collect::<Vec<_>>();
+    let mut v = h.to_vec();
+    v.extend(IV.iter().map(|iv| const_word(s, *iv)));
+    v[12] = const_word(s, IV[4] ^ counter as u32);
+    v[13] = const_word(s, IV[5] ^ (counter >> 32) as u32);
+    if last {
+        v[14] = const_word(s, !IV[6]);
+    }
+
+    for sigma in SIGMA.iter() {
+        g(&mut v, [0, 4, 8, 12], &m[sigma[0]], &m[sigma[1]]);
+        g(&mut v, [1, 5, 9, 13], &m[sigma[2]], &m[sigma[3]]);
+        g(&mut v, [2, 6, 10, 14], &m[sigma[4]], &m[sigma[5]]);
+        g(&mut v, [3, 7, 11, 15], &m[sigma[6]], &m[sigma[7]]);
+        g(&mut v, [0, 5, 10, 15], &m[sigma[8]], &m[sigma[9]]);
+        g(&mut v, [1, 6, 11, 12], &m[sigma[10]], &m[sigma[11]]);
+        g(&mut v, [2, 7, 8, 13], &m[sigma[12]], &m[sigma[13]]);
+        g(&mut v, [3, 4, 9, 14], &m[sigma[14]], &m[sigma[15]]);
+    }
+
+    (0..8).map(|i| xor(&xor(&h[i], &v[i]), &v[i + 8])).collect()
+}
+
+// Unkeyed 32-byte Blake2s digest with the zero salt of the message bits, matching native::blake2s::blake2s.
+// The least significant bit of each byte goes first, so the bits of c_into_bits_le of a number
+// are hashed as its little endian bytes, as in native hash_r of eddsaposeidon. The result is in the same order.
+// The message could be empty, so the constraint system is passed explicitly.
+pub fn c_blake2s<C: CS>(cs: &RCS<C>, bits: &[CBool<C>], personalization: &[u8; 8]) -> Vec<CBool<C>> {
+    assert_eq!(bits.len() % 8, 0, "number of bits should be a multiple of 8");
+    let _scope = Scope::new(cs, "blake2s");
+    let zero = CBool::from_const(cs, &false);
+    let mut h = initial_state(personalization)
+        .iter()
+        .map(|v| const_word(&zero, *v))
+        .collect::<Vec<_>>();
+
+    // the empty message is hashed as one zero block with the zero counter
+    let block_bits = 8 * BLOCK_BYTES;
+    let n_blocks = std::cmp::max((bits.len() + block_bits - 1) / block_bits, 1);
+    for i in 0..n_blocks {
+        let last = i + 1 == n_blocks;
+        // the last block is padded with zeros
+        let mut block = bits.iter().skip(i * block_bits).take(block_bits).cloned().collect::<Vec<_>>();
+        block.resize(block_bits, zero.clone());
+        let counter = if last { bits.len() / 8 } else { (i + 1) * BLOCK_BYTES };
+        h = compress(&h, &block, counter as u64, last);
+    }
+    h.concat()
+}
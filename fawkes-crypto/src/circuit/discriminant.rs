@@ -0,0 +1,147 @@
+use crate::{
+    circuit::{
+        bitify::c_comp_constant,
+        bool::CBool,
+        cs::{CS, RCS},
+        num::CNum,
+    },
+    core::signal::Signal,
+    ff_uint::Num,
+};
+
+// Index of the active variant of an enum signal with N variants.
+// The index is stored as N one-hot bits or as binary encoded bits, if BINARY is set.
+#[derive(Clone, Debug)]
+pub struct CDiscriminant<C: CS, const N: usize, const BINARY: bool> {
+    pub bits: Vec<CBool<C>>,
+    cs: RCS<C>,
+}
+
+impl<C: CS, const N: usize, const BINARY: bool> CDiscriminant<C, N, BINARY> {
+    pub fn num_bits() -> usize {
+        if BINARY {
+            (usize::BITS - N.saturating_sub(1).leading_zeros()) as usize
+        } else {
+            N
+        }
+    }
+
+    fn encode(index: usize) -> Vec<bool> {
+        assert!(index < N, "variant index is out of range");
+        (0..Self::num_bits())
+            .map(|i| if BINARY { (index >> i) & 1 == 1 } else { i == index })
+            .collect()
+    }
+
+    fn decode(bits: &[bool]) -> Option<usize> {
+        if BINARY {
+            let index = bits.iter().rev().fold(0, |acc, &b| (acc << 1) | b as usize);
+            if index < N {
+                Some(index)
+            } else {
+                None
+            }
+        } else if bits.iter().filter(|&&b| b).count() == 1 {
+            bits.iter().position(|&b| b)
+        } else {
+            None
+        }
+    }
+
+    // Return true if the variant with the index is active
+    pub fn is_variant(&self, index: usize) -> CBool<C> {
+        assert!(index < N, "variant index is out of range");
+        if BINARY {
+            let mut acc = CBool::from_const(&self.cs, &true);
+            for (i, b) in self.bits.iter().enumerate() {
+                if (index >> i) & 1 == 1 {
+                    acc &= b;
+                } else {
+                    acc &= &!b.clone();
+                }
+            }
+            acc
+        } else {
+            self.bits[index].clone()
+        }
+    }
+}
+
+impl<C: CS, const N: usize, const BINARY: bool> Signal<C> for CDiscriminant<C, N, BINARY> {
+    type Value = usize;
+
+    fn as_const(&self) -> Option<Self::Value> {
+        let bits = self.bits.iter().map(|b| b.as_const()).collect::<Option<Vec<_>>>()?;
+        Self::decode(&bits)
+    }
+
+    fn get_value(&self) -> Option<Self::Value> {
+        let bits = self.bits.iter().map(|b| b.get_value()).collect::<Option<Vec<_>>>()?;
+        Self::decode(&bits)
+    }
+
+    fn from_const(cs: &RCS<C>, value: &Self::Value) -> Self {
+        let bits = Self::encode(*value).iter().map(|b| CBool::from_const(cs, b)).collect();
+        Self { bits, cs: cs.clone() }
+    }
+
+    fn get_cs(&self) -> &RCS<C> {
+        &self.cs
+    }
+
+    fn alloc(cs: &RCS<C>, value: Option<&Self::Value>) -> Self {
+        let bits = match value {
+            Some(&index) => Self::encode(index).iter().map(|b| CBool::alloc(cs, Some(b))).collect::<Vec<_>>(),
+            None => (0..Self::num_bits()).map(|_| CBool::alloc(cs, None)).collect(),
+        };
+
+        if BINARY {
+            // unused codes are forbidden
+            if N > 1 && !N.is_power_of_two() {
+                c_comp_constant(&bits, Num::from(N as u64 - 1)).assert_const(&false);
+            }
+        } else {
+            let mut sum = CNum::from_const(cs, &Num::ZERO);
+            for b in bits.iter() {
+                sum += b.as_num();
+            }
+            sum.assert_const(&Num::ONE);
+        }
+
+        Self { bits, cs: cs.clone() }
+    }
+
+    fn switch(&self, bit: &CBool<C>, if_else: &Self) -> Self {
+        let bits = self.bits.iter().zip(if_else.bits.iter()).map(|(t, f)| t.switch(bit, f)).collect();
+        Self { bits, cs: self.cs.clone() }
+    }
+
+    fn assert_const(&self, value: &Self::Value) {
+        self.bits.iter().zip(Self::encode(*value).iter()).for_each(|(s, v)| s.assert_const(v));
+    }
+
+    fn assert_eq(&self, other: &Self) {
+        self.bits.iter().zip(other.bits.iter()).for_each(|(s, o)| s.assert_eq(o));
+    }
+
+    fn is_eq(&self, other: &Self) -> CBool<C> {
+        let mut acc = CBool::from_const(&self.cs, &true);
+        for (s, o) in self.bits.iter().zip(other.bits.iter()) {
+            acc &= &s.is_eq(o);
+        }
+        acc
+    }
+
+    fn inputize(&self) {
+        self.bits.iter().for_each(|b| b.inputize());
+    }
+
+    fn write_inputs(value: &Self::Value, inputs: &mut Vec<Num<C::Fr>>) {
+        Self::encode(*value).iter().for_each(|b| CBool::<C>::write_inputs(b, inputs));
+    }
+
+    fn read_inputs(inputs: &mut &[Num<C::Fr>]) -> Option<Self::Value> {
+        let bits = (0..Self::num_bits()).map(|_| CBool::<C>::read_inputs(inputs)).collect::<Option<Vec<_>>>()?;
+        Self::decode(&bits)
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    circuit::{
+        bitify::{c_assert_in_interval, c_assert_range, c_lt},
+        cs::CS,
+        num::CNum,
+        scope::Scope,
+    },
+    core::signal::Signal,
+    ff_uint::{Num, NumRepr, Uint},
+    native::divrem::div_rem,
+};
+
+// Integer division with remainder, returns (a / b, a % b)
+// assuming log2(a) <= limit, log2(b) <= limit
+// The circuit is not satisfiable if b is zero.
+pub fn c_div_rem<C: CS>(a: &CNum<C>, b: &CNum<C>, limit: usize) -> (CNum<C>, CNum<C>) {
+    // q * b + r should not wrap the field
+    assert!(2 * limit + 1 < Num::<C::Fr>::MODULUS_BITS as usize, "limit is too large");
+
+    match (a.as_const(), b.as_const()) {
+        (Some(a_value), Some(b_value)) => {
+            let (q, r) = div_rem(a_value, b_value);
+            (a.derive_const(&q), a.derive_const(&r))
+        }
+        (_, Some(b_value)) => c_div_rem_constant(a, b_value, limit),
+        _ => {
+            let _scope = Scope::new(a.get_cs(), "div_rem");
+            let (q, r) = alloc_div_rem(a, b);
+
+            c_assert_range(&q, limit);
+            c_assert_range(&r, limit);
+            CS::enforce(&q, b, &(a - &r));
+            c_lt(&r, b, limit).assert_const(&true);
+            (q, r)
+        }
+    }
+}
+
+// The range checks are bounded by the divisor, so they are cheaper than in the general case
+fn c_div_rem_constant<C: CS>(a: &CNum<C>, b: Num<C::Fr>, limit: usize) -> (CNum<C>, CNum<C>) {
+    assert!(!b.is_zero(), "division by zero");
+    if b == Num::ONE {
+        return (a.clone(), a.derive_const(&Num::ZERO));
+    }
+    let _scope = Scope::new(a.get_cs(), "div_rem_constant");
+
+    let max_a = (NumRepr::ONE << (limit as u32)) - NumRepr::ONE;
+    let max_q = max_a / b.to_uint();
+    // the divisor is larger than any a, the range check of a ensures a < b
+    if max_q.is_zero() {
+        c_assert_range(a, limit);
+        return (a.derive_const(&Num::ZERO), a.clone());
+    }
+
+    let (q, r) = alloc_div_rem(a, &a.derive_const(&b));
+    c_assert_range(&q, max_q.0.bits());
+    c_assert_in_interval(&r, Num::ZERO, b - Num::ONE);
+    (&q * b - a + &r).assert_zero();
+    (q, r)
+}
+
+fn alloc_div_rem<C: CS>(a: &CNum<C>, b: &CNum<C>) -> (CNum<C>, CNum<C>) {
+    let values = match (a.get_value(), b.get_value()) {
+        // the witness is not valid for zero divisor in any case
+        (Some(a), Some(b)) if b.is_zero() => Some((Num::ZERO, a)),
+        (Some(a), Some(b)) => Some(div_rem(a, b)),
+        _ => None,
+    };
+    let q = a.derive_alloc(values.as_ref().map(|v| &v.0));
+    let r = a.derive_alloc(values.as_ref().map(|v| &v.1));
+    (q, r)
+}
@@ -1,7 +1,7 @@
 // Assuming JubJub curves with cofactor=8 only
 
 use crate::{
-    circuit::{bool::CBool, cs::{CS, RCS}, mux::c_mux3, num::CNum},
+    circuit::{bool::CBool, cs::{CS, RCS}, mux::c_mux3, num::CNum, scope::Scope},
     core::signal::Signal,
     ff_uint::Num,
     native::ecc::{EdwardsPoint, EdwardsPointEx, JubJubParams, MontgomeryPoint},
@@ -55,6 +55,7 @@ impl<C: CS> CEdwardsPoint<C> {
     }
 
     pub fn assert_in_subgroup<J: JubJubParams<Fr = C::Fr>>(&self, params: &J) {
+        let _scope = Scope::new(self.get_cs(), "edwards_assert_in_subgroup");
         let preimage_value = self
             .get_value()
             .map(|p| p.mul(Num::from(8).checked_inv().unwrap(), params));
@@ -67,6 +68,7 @@ impl<C: CS> CEdwardsPoint<C> {
     }
 
     pub fn subgroup_decompress<J: JubJubParams<Fr = C::Fr>>(x: &CNum<C>, params: &J) -> Self {
+        let _scope = Scope::new(x.get_cs(), "edwards_subgroup_decompress");
         let preimage_value = x.get_value().map(|x| {
             EdwardsPoint::subgroup_decompress(x, params)
                 .unwrap_or(params.edwards_g().clone())
@@ -104,6 +106,7 @@ impl<C: CS> CEdwardsPoint<C> {
             vec![x_col, y_col]
         }
         let cs = self.get_cs();
+        let _scope = Scope::new(cs, "edwards_mul");
 
         match self.as_const() {
             Some(c_base) => {
@@ -227,6 +230,7 @@ impl<C: CS> CEdwardsPoint<C> {
             (is_square, y)
         }
 
+        let _scope = Scope::new(t.get_cs(), "edwards_from_scalar");
         let t2g1 = t.square() * params.montgomery_u();
 
         let x3 = -Num::ONE / params.montgomery_a() * (&t2g1 + Num::ONE);
@@ -0,0 +1,175 @@
+use std::ops::{Add, Sub};
+
+use crate::{
+    circuit::{
+        bitify::{c_assert_range, c_ge, c_gt, c_le, c_lt},
+        bool::CBool,
+        cs::{CS, RCS},
+        num::CNum,
+        scope::Scope,
+    },
+    core::signal::{read_input, Signal},
+    ff_uint::{Num, NumRepr},
+    native::fixed::{mul_raw, rounding_offset, Fixed, Rounding},
+};
+
+// Unsigned fixed point number raw / 2^FRAC_BITS. The raw value is range checked to INT_BITS + FRAC_BITS bits
+// at alloc and after each arithmetic operation, so the circuit is not satisfiable if the value overflows.
+#[derive(Clone, Debug)]
+pub struct CFixed<C: CS, const INT_BITS: usize, const FRAC_BITS: usize> {
+    raw: CNum<C>,
+}
+
+impl<C: CS, const INT_BITS: usize, const FRAC_BITS: usize> CFixed<C, INT_BITS, FRAC_BITS> {
+    pub const BITS: usize = INT_BITS + FRAC_BITS;
+
+    fn check_bits() {
+        assert!(FRAC_BITS > 0, "fixed point number should have at least one fractional bit");
+        // the product of two numbers should not wrap the field
+        assert!(
+            2 * Self::BITS + 1 < Num::<C::Fr>::MODULUS_BITS as usize,
+            "fixed point number is too large for the field"
+        );
+    }
+
+    // The caller should ensure, that the raw value is in range
+    pub fn from_raw_unchecked(raw: CNum<C>) -> Self {
+        Self::check_bits();
+        Self { raw }
+    }
+
+    // Range check the raw value
+    pub fn from_raw(raw: &CNum<C>) -> Self {
+        Self::check_bits();
+        c_assert_range(raw, Self::BITS);
+        Self { raw: raw.clone() }
+    }
+
+    // Convert the integer, which is range checked to INT_BITS
+    pub fn from_int(num: &CNum<C>) -> Self {
+        Self::check_bits();
+        c_assert_range(num, INT_BITS);
+        Self { raw: num * Num::from_uint(NumRepr::ONE << FRAC_BITS as u32).unwrap() }
+    }
+
+    pub fn as_raw(&self) -> &CNum<C> {
+        &self.raw
+    }
+
+    pub fn mul(&self, other: &Self, rounding: Rounding) -> Self {
+        if let (Some(a), Some(b)) = (self.as_const(), other.as_const()) {
+            let res = a.checked_mul(&b, rounding).expect("constant is out of range");
+            return self.derive_const(&res);
+        }
+
+        let _scope = Scope::new(self.get_cs(), "fixed_mul");
+        let values = match (self.raw.get_value(), other.raw.get_value()) {
+            (Some(_), Some(_)) => Some(match (self.get_value(), other.get_value()) {
+                (Some(a), Some(b)) => mul_raw(a.raw(), b.raw(), FRAC_BITS, rounding),
+                // the operands are out of range, so the circuit is not satisfiable in any case
+                _ => (Num::ZERO, Num::ZERO),
+            }),
+            _ => None,
+        };
+        let q: CNum<C> = self.derive_alloc(values.as_ref().map(|v| &v.0));
+        let r: CNum<C> = self.derive_alloc(values.as_ref().map(|v| &v.1));
+        c_assert_range(&q, Self::BITS);
+        c_assert_range(&r, FRAC_BITS);
+
+        let k = Num::from_uint(NumRepr::ONE << FRAC_BITS as u32).unwrap();
+        let offset = rounding_offset::<C::Fr>(FRAC_BITS, rounding);
+        CS::enforce(&self.raw, &other.raw, &(&q * k + &r - offset));
+        Self { raw: q }
+    }
+
+    pub fn lt(&self, other: &Self) -> CBool<C> {
+        c_lt(&self.raw, &other.raw, Self::BITS)
+    }
+
+    pub fn le(&self, other: &Self) -> CBool<C> {
+        c_le(&self.raw, &other.raw, Self::BITS)
+    }
+
+    pub fn gt(&self, other: &Self) -> CBool<C> {
+        c_gt(&self.raw, &other.raw, Self::BITS)
+    }
+
+    pub fn ge(&self, other: &Self) -> CBool<C> {
+        c_ge(&self.raw, &other.raw, Self::BITS)
+    }
+}
+
+impl<C: CS, const INT_BITS: usize, const FRAC_BITS: usize> Signal<C> for CFixed<C, INT_BITS, FRAC_BITS> {
+    type Value = Fixed<C::Fr, INT_BITS, FRAC_BITS>;
+
+    fn as_const(&self) -> Option<Self::Value> {
+        self.raw.as_const().map(|v| Fixed::from_raw(v).expect("constant is out of range"))
+    }
+
+    // the value is None, if the raw value is out of range, so the circuit is not satisfiable
+    fn get_value(&self) -> Option<Self::Value> {
+        self.raw.get_value().and_then(Fixed::from_raw)
+    }
+
+    fn from_const(cs: &RCS<C>, value: &Self::Value) -> Self {
+        Self::from_raw_unchecked(CNum::from_const(cs, &value.raw()))
+    }
+
+    fn get_cs(&self) -> &RCS<C> {
+        self.raw.get_cs()
+    }
+
+    fn alloc(cs: &RCS<C>, value: Option<&Self::Value>) -> Self {
+        Self::from_raw(&CNum::alloc(cs, value.map(|v| v.raw()).as_ref()))
+    }
+
+    fn switch(&self, bit: &CBool<C>, if_else: &Self) -> Self {
+        Self::from_raw_unchecked(self.raw.switch(bit, &if_else.raw))
+    }
+
+    fn assert_const(&self, value: &Self::Value) {
+        self.raw.assert_const(&value.raw())
+    }
+
+    fn assert_eq(&self, other: &Self) {
+        self.raw.assert_eq(&other.raw)
+    }
+
+    fn is_eq(&self, other: &Self) -> CBool<C> {
+        self.raw.is_eq(&other.raw)
+    }
+
+    fn inputize(&self) {
+        self.raw.inputize()
+    }
+
+    fn write_inputs(value: &Self::Value, inputs: &mut Vec<Num<C::Fr>>) {
+        inputs.push(value.raw());
+    }
+
+    fn read_inputs(inputs: &mut &[Num<C::Fr>]) -> Option<Self::Value> {
+        Fixed::from_raw(read_input(inputs)?)
+    }
+}
+
+impl<C: CS, const INT_BITS: usize, const FRAC_BITS: usize> Add<&CFixed<C, INT_BITS, FRAC_BITS>>
+    for &CFixed<C, INT_BITS, FRAC_BITS>
+{
+    type Output = CFixed<C, INT_BITS, FRAC_BITS>;
+
+    // the sum is range checked
+    fn add(self, other: &CFixed<C, INT_BITS, FRAC_BITS>) -> Self::Output {
+        CFixed::from_raw(&(&self.raw + &other.raw))
+    }
+}
+
+impl<C: CS, const INT_BITS: usize, const FRAC_BITS: usize> Sub<&CFixed<C, INT_BITS, FRAC_BITS>>
+    for &CFixed<C, INT_BITS, FRAC_BITS>
+{
+    type Output = CFixed<C, INT_BITS, FRAC_BITS>;
+
+    // underflow wraps to a large field element, so it is caught by the range check
+    fn sub(self, other: &CFixed<C, INT_BITS, FRAC_BITS>) -> Self::Output {
+        CFixed::from_raw(&(&self.raw - &other.raw))
+    }
+}
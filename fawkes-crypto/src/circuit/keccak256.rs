@@ -0,0 +1,111 @@
+use crate::{
+    circuit::{bitify::c_into_bits_le, bool::CBool, cs::CS, scope::Scope, word::xor_bit},
+    core::signal::Signal,
+    native::{
+        bitify::bytes_to_bits_be,
+        keccak256::{keccak256_padding, pi_index, RATE, ROTATION_OFFSETS, ROUND_CONSTANTS},
+    },
+};
+
+// 64-bit lane, little endian bits
+type Lane<C> = Vec<CBool<C>>;
+
+// The lowest bit of the sum of 5 bits, 3 constraints instead of 4 for the chain of xors: c_into_bits_le allocates
+// the 2 upper bits and the lowest bit is the rest of the sum, so there are only 3 booleanity checks
+// and no separate packing constraint
+fn parity5<C: CS>(bits: [&CBool<C>; 5]) -> CBool<C> {
+    let mut sum = bits[0].to_num();
+    for b in bits[1..].iter() {
+        sum += b.as_num();
+    }
+    c_into_bits_le(&sum, 3)[0].clone()
+}
+
+fn round<C: CS>(a: &[Lane<C>], rc: u64) -> Vec<Lane<C>> {
+    // theta
+    let c = (0..5)
+        .map(|x| {
+            (0..64)
+                .map(|z| parity5([&a[x][z], &a[x + 5][z], &a[x + 10][z], &a[x + 15][z], &a[x + 20][z]]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let d = (0..5)
+        .map(|x| {
+            (0..64)
+                .map(|z| xor_bit(c[(x + 4) % 5][z].as_num(), c[(x + 1) % 5][(z + 63) % 64].as_num()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // rho and pi are the permutation of the bits, so they are applied with theta
+    let mut b = vec![vec![]; 25];
+    for (i, lane) in a.iter().enumerate() {
+        let r = ROTATION_OFFSETS[i] as usize;
+        b[pi_index(i)] = (0..64)
+            .map(|z| {
+                let z = (z + 64 - r) % 64;
+                xor_bit(lane[z].as_num(), d[i % 5][z].as_num())
+            })
+            .collect();
+    }
+
+    // chi, b0 xor (!b1 and b2) = b0 xor (b2 - b1 * b2), 2 constraints per bit
+    let mut res = (0..25)
+        .map(|i| {
+            let y = i - i % 5;
+            let (b0, b1, b2) = (&b[i], &b[y + (i + 1) % 5], &b[y + (i + 2) % 5]);
+            (0..64)
+                .map(|z| xor_bit(b0[z].as_num(), &(b2[z].as_num() - b1[z].as_num() * b2[z].as_num())))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // iota, the negation is free
+    for (z, bit) in res[0].iter_mut().enumerate() {
+        if (rc >> z) & 1 == 1 {
+            *bit = !&*bit;
+        }
+    }
+    res
+}
+
+fn permute<C: CS>(state: Vec<Lane<C>>) -> Vec<Lane<C>> {
+    ROUND_CONSTANTS.iter().fold(state, |s, rc| round(&s, *rc))
+}
+
+// Keccak-f[1600] permutation. The state bits are ordered as in the specification: the lane x + 5 * y
+// holds the bits 64 * (x + 5 * y)..64 * (x + 5 * y + 1), the least significant bit goes first.
+pub fn c_keccak_f1600<C: CS>(state: &[CBool<C>]) -> Vec<CBool<C>> {
+    assert!(state.len() == 1600, "state should be 1600 bits");
+    let _scope = Scope::new(state[0].get_cs(), "keccak_f1600");
+    permute(state.chunks(64).map(|c| c.to_vec()).collect()).concat()
+}
+
+// Converts the big endian bits of the bytes to the little endian ones and back
+fn swap_bit_order<C: CS>(bits: &[CBool<C>]) -> Vec<CBool<C>> {
+    bits.chunks(8).flat_map(|c| c.iter().rev().cloned()).collect()
+}
+
+// Keccak-256 as used by Ethereum of the message bits, the most significant bit of each byte goes first.
+// The padding depends only on the length, so it is constant.
+pub fn c_keccak256<C: CS>(bits: &[CBool<C>]) -> Vec<CBool<C>> {
+    assert!(!bits.is_empty(), "should be at least one input bit");
+    assert_eq!(bits.len() % 8, 0, "number of bits should be a multiple of 8");
+    let _scope = Scope::new(bits[0].get_cs(), "keccak256");
+    let padding = bytes_to_bits_be(&keccak256_padding(bits.len() / 8))
+        .iter()
+        .map(|b| bits[0].derive_const(b))
+        .collect::<Vec<_>>();
+    let message = swap_bit_order(&[bits, &padding].concat());
+
+    let zero: CBool<C> = bits[0].derive_const(&false);
+    let mut state = vec![vec![zero; 64]; 25];
+    for block in message.chunks(8 * RATE) {
+        for (lane, chunk) in state.iter_mut().zip(block.chunks(64)) {
+            *lane = lane.iter().zip(chunk.iter()).map(|(s, m)| xor_bit(s.as_num(), m.as_num())).collect();
+        }
+        state = permute(state);
+    }
+    swap_bit_order(&state[..4].concat())
+}
@@ -0,0 +1,53 @@
+use crate::{
+    circuit::{cs::CS, num::CNum, scope::Scope},
+    core::signal::Signal,
+    ff_uint::Num,
+    native::mimc::MimcParams,
+};
+
+// Square and multiply, the number of constraints is the number of bits of exp plus the number of ones minus 2
+pub fn c_pow<C: CS>(a: &CNum<C>, exp: u64) -> CNum<C> {
+    assert!(exp > 0, "exponent should be positive");
+    let mut res = a.clone();
+    for i in (0..63 - exp.leading_zeros()).rev() {
+        res = res.square();
+        if (exp >> i) & 1 == 1 {
+            res = &res * a;
+        }
+    }
+    res
+}
+
+pub fn c_mimc_feistel<C: CS>(
+    xl: &CNum<C>,
+    xr: &CNum<C>,
+    k: &CNum<C>,
+    params: &MimcParams<C::Fr>,
+) -> (CNum<C>, CNum<C>) {
+    let _scope = Scope::new(xl.get_cs(), "mimc_feistel");
+    let (mut xl, mut xr) = (xl.clone(), xr.clone());
+    let rounds = params.c.len();
+    for (i, c) in params.c.iter().enumerate() {
+        let t = &xr + c_pow(&(&xl + k + c), params.alpha);
+        if i + 1 < rounds {
+            xr = xl;
+            xl = t;
+        } else {
+            xr = t;
+        }
+    }
+    (xl, xr)
+}
+
+pub fn c_mimc_sponge<C: CS>(inputs: &[CNum<C>], k: &CNum<C>, params: &MimcParams<C::Fr>) -> CNum<C> {
+    assert!(!inputs.is_empty(), "should be at least one input");
+    let cs = inputs[0].get_cs();
+    let _scope = Scope::new(cs, "mimc_sponge");
+    let (mut xl, mut xr) = (inputs[0].derive_const(&Num::ZERO), inputs[0].derive_const(&Num::ZERO));
+    for input in inputs.iter() {
+        let res = c_mimc_feistel(&(&xl + input), &xr, k, params);
+        xl = res.0;
+        xr = res.1;
+    }
+    xl
+}
@@ -11,7 +11,19 @@ mod plonk;
 pub use plonk::*;
 
 pub mod bitify;
+pub mod blake2s;
+pub mod discriminant;
+pub mod divrem;
 pub mod ecc;
 pub mod eddsaposeidon;
+pub mod fixed;
+pub mod keccak256;
+pub mod mimc;
 pub mod mux;
+pub mod nonnative;
+pub mod pedersen;
+pub mod uint;
 pub mod poseidon;
+pub mod rescue;
+pub mod sha256;
+pub mod word;
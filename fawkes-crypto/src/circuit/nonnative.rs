@@ -0,0 +1,409 @@
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::{
+    circuit::{
+        bitify::{c_assert_range, c_from_bits_le, c_into_bits_le_strict, c_lt_constant},
+        bool::CBool,
+        cs::{CS, RCS},
+        num::CNum,
+        scope::Scope,
+    },
+    core::signal::{read_input, Signal},
+    ff_uint::{construct_uint, Num, NumRepr, PrimeField, PrimeFieldParams, Uint},
+};
+
+construct_uint! {
+    struct U1024(16);
+}
+
+// Integer, which is wide enough for the product of two unreduced elements
+type Wide = NumRepr<U1024>;
+
+pub const LIMB_BITS: usize = 64;
+
+fn ceil_log2(n: usize) -> usize {
+    (usize::BITS - n.saturating_sub(1).leading_zeros()) as usize
+}
+
+fn to_wide<Fr: PrimeField>(v: Num<Fr>) -> Wide {
+    NumRepr(v.to_uint().0.to_other().unwrap())
+}
+
+fn from_wide<Fr: PrimeField>(v: Wide) -> Option<Num<Fr>> {
+    Num::from_uint(NumRepr(v.0.to_other()?))
+}
+
+fn wide_limb(v: Wide, i: usize) -> u64 {
+    v.0.as_inner().as_ref()[i]
+}
+
+fn wide_from_limbs<Fr: PrimeField>(limbs: &[Num<Fr>]) -> Wide {
+    limbs
+        .iter()
+        .rev()
+        .fold(Wide::ZERO, |acc, l| (acc << LIMB_BITS as u32) + to_wide(*l))
+}
+
+fn modulus<F: PrimeField>() -> Wide {
+    NumRepr(F::MODULUS.to_other().unwrap())
+}
+
+fn add_limbs<C: CS>(a: &[CNum<C>], b: &[CNum<C>]) -> Vec<CNum<C>> {
+    a.iter().zip(b.iter()).map(|(a, b)| a + b).collect()
+}
+
+fn sub_limbs<C: CS>(a: &[CNum<C>], b: &[CNum<C>]) -> Vec<CNum<C>> {
+    a.iter().zip(b.iter()).map(|(a, b)| a - b).collect()
+}
+
+// Coefficients of the product of the polynomials with the limbs as coefficients
+fn mul_coeffs<C: CS>(a: &[CNum<C>], b: &[CNum<C>]) -> Vec<CNum<C>> {
+    let zero = a[0].derive_const(&Num::ZERO);
+    let mut coeffs = vec![zero; a.len() + b.len() - 1];
+    for (i, a_i) in a.iter().enumerate() {
+        for (j, b_j) in b.iter().enumerate() {
+            coeffs[i + j] += a_i * b_j;
+        }
+    }
+    coeffs
+}
+
+// How the remainder of the reduction is constrained
+#[derive(Clone, Copy, PartialEq)]
+enum Reduction {
+    // the remainder is zero
+    Zero,
+    // the remainder has normalized limbs, but could be greater than the modulus
+    Lazy,
+    // the remainder is less than the modulus
+    Canonical,
+}
+
+// Element of the prime field F, emulated over C::Fr as little endian limbs of LIMB_BITS bits.
+// Addition and subtraction are lazy: the limbs grow and are reduced only when it is needed to keep
+// the product of two elements below the native modulus. Every limb is less than 2^max_limb_bits.
+#[derive(Clone, Debug)]
+pub struct CNonNative<C: CS, F: PrimeField> {
+    limbs: Vec<CNum<C>>,
+    max_limb_bits: usize,
+    phantom: PhantomData<F>,
+}
+
+impl<C: CS, F: PrimeField> CNonNative<C, F> {
+    pub fn num_limbs() -> usize {
+        (F::MODULUS_BITS as usize + LIMB_BITS - 1) / LIMB_BITS
+    }
+
+    // The bound of the limbs, for which the coefficients of the product do not wrap the native field
+    fn limb_bits_limit() -> usize {
+        (C::Fr::MODULUS_BITS as usize - 8 - ceil_log2(Self::num_limbs())) / 2
+    }
+
+    fn check_params() {
+        assert!(Self::limb_bits_limit() > LIMB_BITS + 2, "native field is too small");
+        let value_bits = Self::limb_bits_limit() + LIMB_BITS * (Self::num_limbs() - 1);
+        assert!(2 * value_bits + 2 < U1024::NUM_WORDS * U1024::WORD_BITS, "emulated field is too large");
+    }
+
+    fn new_unchecked(limbs: Vec<CNum<C>>, max_limb_bits: usize) -> Self {
+        Self::check_params();
+        Self { limbs, max_limb_bits, phantom: PhantomData }
+    }
+
+    pub fn limbs(&self) -> &[CNum<C>] {
+        &self.limbs
+    }
+
+    fn limbs_value(limbs: &[CNum<C>]) -> Option<Wide> {
+        let values = limbs.iter().map(|l| l.get_value()).collect::<Option<Vec<_>>>()?;
+        Some(wide_from_limbs(&values))
+    }
+
+    fn const_limbs(cs: &RCS<C>, value: Wide, n: usize) -> Vec<CNum<C>> {
+        (0..n).map(|i| CNum::from_const(cs, &Num::from(wide_limb(value, i)))).collect()
+    }
+
+    // Allocate the limbs of the value and range check them to LIMB_BITS
+    fn alloc_limbs(cs: &RCS<C>, value: Option<Wide>, n: usize) -> Vec<CNum<C>> {
+        (0..n)
+            .map(|i| {
+                let limb = CNum::alloc(cs, value.map(|v| Num::from(wide_limb(v, i))).as_ref());
+                c_assert_range(&limb, LIMB_BITS);
+                limb
+            })
+            .collect()
+    }
+
+    // Convert the native number, the result is the number modulo the modulus of F
+    pub fn from_num(num: &CNum<C>) -> Self {
+        let _scope = Scope::new(num.get_cs(), "nonnative_from_num");
+        let bits = c_into_bits_le_strict(num);
+        let limbs = bits.chunks(LIMB_BITS).map(c_from_bits_le).collect::<Vec<_>>();
+        if limbs.len() > Self::num_limbs() {
+            let limbs = Self::c_reduce(num.get_cs(), &limbs, LIMB_BITS, Reduction::Lazy);
+            Self::new_unchecked(limbs, LIMB_BITS)
+        } else {
+            let mut limbs = limbs;
+            limbs.resize(Self::num_limbs(), num.derive_const(&Num::ZERO));
+            Self::new_unchecked(limbs, LIMB_BITS)
+        }
+    }
+
+    // Enforce sum coeffs[k] * 2^(LIMB_BITS * k) = q * p + r, where the coefficients are less than 2^coeff_bits.
+    // Return the limbs of r.
+    fn c_reduce(cs: &RCS<C>, coeffs: &[CNum<C>], coeff_bits: usize, reduction: Reduction) -> Vec<CNum<C>> {
+        let n = Self::num_limbs();
+        let p = modulus::<F>();
+        let value = Self::limbs_value(coeffs);
+        let (q_value, r_value) = match value {
+            Some(v) => (Some(v / p), Some(v % p)),
+            None => (None, None),
+        };
+
+        let value_bits = coeff_bits + LIMB_BITS * (coeffs.len() - 1) + 1;
+        let q_bits = value_bits.saturating_sub(F::MODULUS_BITS as usize - 1).max(1);
+        let q_len = (q_bits + LIMB_BITS - 1) / LIMB_BITS;
+        let q = Self::alloc_limbs(cs, q_value, q_len);
+        c_assert_range(&q[q_len - 1], q_bits - LIMB_BITS * (q_len - 1));
+
+        let r = if reduction == Reduction::Zero {
+            vec![]
+        } else {
+            Self::alloc_limbs(cs, r_value, n)
+        };
+        if reduction == Reduction::Canonical {
+            Self::c_lt_modulus(cs, &r).assert_const(&true);
+        }
+
+        // the coefficients of sum coeffs[k] * X^k - q(X) * p(X) - r(X) are in (-2^diff_bits, 2^diff_bits)
+        let len = coeffs.len().max(q_len + n - 1).max(n);
+        let zero = CNum::from_const(cs, &Num::ZERO);
+        let diff = (0..len)
+            .map(|k| {
+                let mut acc = coeffs.get(k).unwrap_or(&zero).clone();
+                for (i, q_i) in q.iter().enumerate().take(k + 1) {
+                    if k - i < n {
+                        acc -= q_i * Num::from(wide_limb(p, k - i));
+                    }
+                }
+                if let Some(r_k) = r.get(k) {
+                    acc -= r_k;
+                }
+                acc
+            })
+            .collect::<Vec<_>>();
+        let diff_bits = coeff_bits.max(2 * LIMB_BITS + ceil_log2(q_len.min(n)) + 1);
+
+        // the sum is zero, if the carries to the next limb are consistent and the last carry is zero
+        let carry_bits = diff_bits + 1 - LIMB_BITS;
+        let carry_offset = Num::from_uint(NumRepr::ONE << carry_bits as u32).unwrap();
+        let shift = Num::from_uint(NumRepr::ONE << LIMB_BITS as u32).unwrap();
+        let shift_inv = shift.checked_inv().unwrap();
+        let mut carry = zero;
+        for d in diff[..len - 1].iter() {
+            let acc = d + &carry;
+            let next: CNum<C> = acc.derive_alloc(acc.get_value().map(|v| v * shift_inv).as_ref());
+            c_assert_range(&(&next + carry_offset), carry_bits + 1);
+            (acc - &next * shift).assert_zero();
+            carry = next;
+        }
+        (&diff[len - 1] + &carry).assert_zero();
+        r
+    }
+
+    // Return true if the normalized limbs are less than the modulus
+    fn c_lt_modulus(cs: &RCS<C>, limbs: &[CNum<C>]) -> CBool<C> {
+        let p = modulus::<F>();
+        let mut acc = CBool::from_const(cs, &false);
+        for (i, limb) in limbs.iter().enumerate() {
+            let p_i = Num::from(wide_limb(p, i));
+            let lt = c_lt_constant(limb, p_i, LIMB_BITS);
+            let eq = limb.is_eq(&limb.derive_const(&p_i));
+            acc = lt | (eq & acc);
+        }
+        acc
+    }
+
+    // Reduce the limbs to LIMB_BITS, the value could be not less than the modulus
+    pub fn reduce(&self) -> Self {
+        if self.max_limb_bits <= LIMB_BITS {
+            return self.clone();
+        }
+        let _scope = Scope::new(self.get_cs(), "nonnative_reduce");
+        let limbs = Self::c_reduce(self.get_cs(), &self.limbs, self.max_limb_bits, Reduction::Lazy);
+        Self::new_unchecked(limbs, LIMB_BITS)
+    }
+
+    // The unique representation of the value, the limbs are less than the modulus
+    fn canonical_limbs(&self) -> Vec<CNum<C>> {
+        let _scope = Scope::new(self.get_cs(), "nonnative_canonical");
+        Self::c_reduce(self.get_cs(), &self.limbs, self.max_limb_bits, Reduction::Canonical)
+    }
+
+    // Enforce, that the value is zero modulo p
+    fn assert_zero(&self) {
+        let _scope = Scope::new(self.get_cs(), "nonnative_assert_zero");
+        Self::c_reduce(self.get_cs(), &self.limbs, self.max_limb_bits, Reduction::Zero);
+    }
+
+    // Limbs of k * p, each of them is at least 2^bits, so they could be used to subtract a value with such limbs
+    fn zero_offset(&self, bits: usize) -> Vec<CNum<C>> {
+        let n = Self::num_limbs();
+        let p = modulus::<F>();
+        let base = (0..n).fold(Wide::ZERO, |acc, i| acc + (Wide::ONE << (bits + LIMB_BITS * i) as u32));
+        let m = (p - base % p) % p;
+        (0..n)
+            .map(|i| {
+                let limb = (NumRepr::ONE << bits as u32) + NumRepr::from(wide_limb(m, i));
+                self.derive_const(&Num::from_uint(limb).unwrap())
+            })
+            .collect()
+    }
+
+    pub fn inv(&self) -> Self {
+        if let Some(v) = self.as_const() {
+            return self.derive_const(&v.checked_inv().expect("inverse of zero"));
+        }
+        let _scope = Scope::new(self.get_cs(), "nonnative_inv");
+        let value = self.get_value().map(|v| v.checked_inv().unwrap_or(Num::ZERO));
+        let res: Self = self.derive_alloc(value.as_ref());
+
+        // self * res - 1 is zero modulo p, the offset p - 1 keeps the coefficients nonnegative
+        let n = Self::num_limbs();
+        let minus_one = Self::const_limbs(self.get_cs(), modulus::<F>() - Wide::ONE, n);
+        let mut coeffs = mul_coeffs(&self.limbs, &res.limbs);
+        coeffs.iter_mut().zip(minus_one.iter()).for_each(|(c, m)| *c += m);
+        let coeff_bits = self.max_limb_bits + LIMB_BITS + ceil_log2(n) + 1;
+        Self::c_reduce(self.get_cs(), &coeffs, coeff_bits, Reduction::Zero);
+        res
+    }
+}
+
+impl<C: CS, F: PrimeField> Signal<C> for CNonNative<C, F> {
+    type Value = Num<F>;
+
+    fn as_const(&self) -> Option<Self::Value> {
+        let values = self.limbs.iter().map(|l| l.as_const()).collect::<Option<Vec<_>>>()?;
+        from_wide(wide_from_limbs(&values) % modulus::<F>())
+    }
+
+    fn get_value(&self) -> Option<Self::Value> {
+        from_wide(Self::limbs_value(&self.limbs)? % modulus::<F>())
+    }
+
+    fn from_const(cs: &RCS<C>, value: &Self::Value) -> Self {
+        let value = NumRepr(value.to_uint().0.to_other().unwrap());
+        Self::new_unchecked(Self::const_limbs(cs, value, Self::num_limbs()), LIMB_BITS)
+    }
+
+    fn get_cs(&self) -> &RCS<C> {
+        self.limbs[0].get_cs()
+    }
+
+    fn alloc(cs: &RCS<C>, value: Option<&Self::Value>) -> Self {
+        let value = value.map(|v| NumRepr(v.to_uint().0.to_other().unwrap()));
+        Self::new_unchecked(Self::alloc_limbs(cs, value, Self::num_limbs()), LIMB_BITS)
+    }
+
+    fn switch(&self, bit: &CBool<C>, if_else: &Self) -> Self {
+        let limbs = self
+            .limbs
+            .iter()
+            .zip(if_else.limbs.iter())
+            .map(|(t, f)| t.switch(bit, f))
+            .collect();
+        Self::new_unchecked(limbs, self.max_limb_bits.max(if_else.max_limb_bits))
+    }
+
+    fn assert_const(&self, value: &Self::Value) {
+        self.assert_eq(&self.derive_const(value))
+    }
+
+    fn assert_eq(&self, other: &Self) {
+        (self - other).assert_zero()
+    }
+
+    fn is_eq(&self, other: &Self) -> CBool<C> {
+        let limbs = (self - other).canonical_limbs();
+        let mut sum = self.derive_const::<CNum<C>>(&Num::ZERO);
+        for l in limbs.iter() {
+            sum += l;
+        }
+        // the limbs are small, so their sum is zero only if all of them are zero
+        sum.is_zero()
+    }
+
+    fn inputize(&self) {
+        self.canonical_limbs().iter().for_each(|l| l.inputize());
+    }
+
+    fn write_inputs(value: &Self::Value, inputs: &mut Vec<Num<C::Fr>>) {
+        let value = NumRepr(value.to_uint().0.to_other().unwrap());
+        (0..Self::num_limbs()).for_each(|i| inputs.push(Num::from(wide_limb(value, i))));
+    }
+
+    fn read_inputs(inputs: &mut &[Num<C::Fr>]) -> Option<Self::Value> {
+        let limbs = (0..Self::num_limbs()).map(|_| read_input(inputs)).collect::<Option<Vec<_>>>()?;
+        if limbs.iter().any(|l| (l.to_uint() >> LIMB_BITS as u32) != NumRepr::ZERO) {
+            return None;
+        }
+        let value = wide_from_limbs(&limbs);
+        if value < modulus::<F>() {
+            from_wide(value)
+        } else {
+            None
+        }
+    }
+}
+
+impl<C: CS, F: PrimeField> Add<&CNonNative<C, F>> for &CNonNative<C, F> {
+    type Output = CNonNative<C, F>;
+
+    fn add(self, other: &CNonNative<C, F>) -> Self::Output {
+        let bits = self.max_limb_bits.max(other.max_limb_bits) + 1;
+        if bits > CNonNative::<C, F>::limb_bits_limit() {
+            return &self.reduce() + &other.reduce();
+        }
+        CNonNative::new_unchecked(add_limbs(&self.limbs, &other.limbs), bits)
+    }
+}
+
+impl<C: CS, F: PrimeField> Sub<&CNonNative<C, F>> for &CNonNative<C, F> {
+    type Output = CNonNative<C, F>;
+
+    // self + k * p - other, so the limbs are nonnegative
+    fn sub(self, other: &CNonNative<C, F>) -> Self::Output {
+        let bits = self.max_limb_bits.max(other.max_limb_bits + 1) + 1;
+        if bits > CNonNative::<C, F>::limb_bits_limit() {
+            return &self.reduce() - &other.reduce();
+        }
+        let offset = self.zero_offset(other.max_limb_bits);
+        let limbs = add_limbs(&self.limbs, &offset);
+        CNonNative::new_unchecked(sub_limbs(&limbs, &other.limbs), bits)
+    }
+}
+
+impl<C: CS, F: PrimeField> Neg for &CNonNative<C, F> {
+    type Output = CNonNative<C, F>;
+
+    fn neg(self) -> Self::Output {
+        &self.derive_const(&Num::ZERO) - self
+    }
+}
+
+impl<C: CS, F: PrimeField> Mul<&CNonNative<C, F>> for &CNonNative<C, F> {
+    type Output = CNonNative<C, F>;
+
+    // the product is reduced to the normalized limbs
+    fn mul(self, other: &CNonNative<C, F>) -> Self::Output {
+        if let (Some(a), Some(b)) = (self.as_const(), other.as_const()) {
+            return self.derive_const(&(a * b));
+        }
+        let _scope = Scope::new(self.get_cs(), "nonnative_mul");
+        let coeffs = mul_coeffs(&self.limbs, &other.limbs);
+        let coeff_bits = self.max_limb_bits + other.max_limb_bits + ceil_log2(CNonNative::<C, F>::num_limbs());
+        let limbs = CNonNative::<C, F>::c_reduce(self.get_cs(), &coeffs, coeff_bits, Reduction::Lazy);
+        CNonNative::new_unchecked(limbs, LIMB_BITS)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    circuit::{
+        bool::CBool,
+        cs::CS,
+        ecc::{CEdwardsPoint, CMontgomeryPoint},
+        mux::c_mux3,
+        scope::Scope,
+    },
+    core::signal::Signal,
+    ff_uint::Num,
+    native::{
+        ecc::{EdwardsPointEx, JubJubParams, MontgomeryPoint},
+        pedersen::{window_bases, PedersenParams, SEGMENT_BITS, WINDOW_BITS},
+    },
+};
+
+// Montgomery coordinates of the multiples 1..8 of the base
+fn gen_table<J: JubJubParams>(base: &EdwardsPointEx<J::Fr>, params: &J) -> Vec<Vec<Num<J::Fr>>> {
+    let mut x_col = vec![];
+    let mut y_col = vec![];
+    let mut q = *base;
+    for _ in 0..8 {
+        let MontgomeryPoint { x, y } = q.into_montgomery().unwrap();
+        x_col.push(x);
+        y_col.push(y);
+        q = q.add(base, params);
+    }
+    vec![x_col, y_col]
+}
+
+// The partial sums of the segment are never equal to the added point or its negation and never zero,
+// so the incomplete Montgomery addition is used for the windows. The segments are added in Edwards form.
+pub fn c_pedersen_hash<C: CS, J: JubJubParams<Fr = C::Fr>>(
+    bits: &[CBool<C>],
+    params: &PedersenParams<C::Fr>,
+    jubjub_params: &J,
+) -> CEdwardsPoint<C> {
+    assert!(!bits.is_empty(), "should be at least one input bit");
+    let n_segments = (bits.len() + SEGMENT_BITS - 1) / SEGMENT_BITS;
+    assert!(n_segments <= params.generators.len(), "not enough generators");
+    let cs = bits[0].get_cs();
+    let _scope = Scope::new(cs, "pedersen_hash");
+
+    let mut acc: Option<CEdwardsPoint<C>> = None;
+    for (segment, g) in bits.chunks(SEGMENT_BITS).zip(params.generators.iter()) {
+        let mut segment_acc: Option<CMontgomeryPoint<C>> = None;
+        for (window, base) in segment.chunks(WINDOW_BITS).zip(window_bases(g).iter()) {
+            // the last window is padded with zeros
+            let mut window = window.to_vec();
+            window.resize(WINDOW_BITS, CBool::from_const(cs, &false));
+
+            let table = gen_table(base, jubjub_params);
+            let res = c_mux3(&window[..3], &table);
+            // the negation of the Montgomery point is (x, -y)
+            let y = &res[1] - &res[1] * window[3].as_num() * Num::from(2);
+            let p = CMontgomeryPoint { x: res[0].clone(), y };
+
+            segment_acc = Some(match segment_acc {
+                Some(segment_acc) => segment_acc.add(&p, jubjub_params),
+                None => p,
+            });
+        }
+
+        let segment_point = segment_acc.unwrap().into_edwards();
+        acc = Some(match acc {
+            Some(acc) => acc.add(&segment_point, jubjub_params),
+            None => segment_point,
+        });
+    }
+    acc.unwrap()
+}
@@ -1,5 +1,5 @@
 use crate::{
-    circuit::{bool::CBool, cs::{CS, RCS}, num::CNum},
+    circuit::{bool::CBool, cs::{CS, RCS}, num::CNum, scope::Scope},
     core::{signal::Signal, sizedvec::SizedVec},
     ff_uint::Num,
     native::poseidon::{MerkleProof, PoseidonParams},
@@ -60,6 +60,7 @@ pub fn c_poseidon<C: CS>(inputs: &[CNum<C>], params: &PoseidonParams<C::Fr>) ->
     );
     assert!(n_inputs > 0, "number of inputs should be positive nonzero");
     let cs = inputs[0].get_cs();
+    let _scope = Scope::new(cs, "poseidon");
     let mut state = vec![CNum::from_const(cs, &Num::ZERO); params.t];
     (&mut state[0..n_inputs]).clone_from_slice(inputs);
 
@@ -70,6 +71,7 @@ pub fn c_poseidon<C: CS>(inputs: &[CNum<C>], params: &PoseidonParams<C::Fr>) ->
 
 pub fn c_poseidon_sponge<C: CS>(inputs: &[CNum<C>], params: &PoseidonParams<C::Fr>) -> CNum<C> {
     let cs = inputs[0].get_cs();
+    let _scope = Scope::new(cs, "poseidon_sponge");
     let mut state = vec![CNum::from_const(cs, &Num::ZERO); params.t];
     let size = CNum::from_const(cs, &Num::from(inputs.len() as u64));
     core::iter::once(&size).chain(inputs.iter()).chunks(params.t-1).into_iter().for_each(|c| {
@@ -85,6 +87,7 @@ pub fn c_poseidon_merkle_proof_root<C: CS, const L: usize>(
     proof: &CMerkleProof<C, L>,
     params: &PoseidonParams<C::Fr>,
 ) -> CNum<C> {
+    let _scope = Scope::new(leaf.get_cs(), "poseidon_merkle_proof_root");
     let mut root = leaf.clone();
     for (p, s) in proof.path.iter().zip(proof.sibling.iter()) {
         let first = s.switch(p, &root);
@@ -101,6 +104,7 @@ pub fn c_poseidon_merkle_tree_root<C: CS>(
     let leaf_sz = leaf.len();
     assert!(leaf_sz > 0, "should be at least one leaf in the tree");
     let cs = leaf[0].get_cs();
+    let _scope = Scope::new(cs, "poseidon_merkle_tree_root");
     let proof_sz = std::mem::size_of::<usize>() * 8 - (leaf_sz - 1).leading_zeros() as usize;
     let total_leaf_sz = 1usize << proof_sz;
     let mut state = leaf.to_vec();
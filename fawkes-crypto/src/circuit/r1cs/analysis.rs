@@ -0,0 +1,164 @@
+use crate::{
+    circuit::{
+        cs::BuildCS,
+        gates::Gate,
+        lc::{AbstractLC, Index},
+    },
+    core::signal::Signal,
+    ff_uint::{Num, PrimeField},
+};
+
+// Result of the under-constrained signals analysis. All indices are aux variable indices.
+#[derive(Clone, Debug, Default)]
+pub struct AnalysisReport {
+    pub num_gates: usize,
+    pub num_input: usize,
+    pub num_aux: usize,
+    // aux variables, which are not used in any gate
+    pub unused_aux: Vec<u32>,
+    // aux variables, which are used only in one gate and only linearly there, with the gate index
+    pub single_linear: Vec<(u32, usize)>,
+    // aux variables, used as booleans without booleanity constraint
+    pub unchecked_bits: Vec<u32>,
+}
+
+impl AnalysisReport {
+    pub fn is_ok(&self) -> bool {
+        self.unused_aux.is_empty() && self.single_linear.is_empty() && self.unchecked_bits.is_empty()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Usage {
+    num_gates: usize,
+    last_gate: usize,
+    nonlinear: bool,
+}
+
+fn is_const_lc<Fr: PrimeField>(lc: &[(Num<Fr>, Index)]) -> bool {
+    lc.iter().all(|(_, i)| *i == Index::Input(0))
+}
+
+fn contains<Fr: PrimeField>(lc: &[(Num<Fr>, Index)], index: Index) -> bool {
+    lc.iter().any(|(_, i)| *i == index)
+}
+
+// represent lc as k*x + b, return None if lc depends on other variables
+fn as_affine<Fr: PrimeField>(lc: &[(Num<Fr>, Index)], x: Index) -> Option<(Num<Fr>, Num<Fr>)> {
+    let mut k = Num::ZERO;
+    let mut b = Num::ZERO;
+    for (v, i) in lc.iter() {
+        if *i == x {
+            k += *v;
+        } else if *i == Index::Input(0) {
+            b += *v;
+        } else {
+            return None;
+        }
+    }
+    Some((k, b))
+}
+
+// check that the gate is equivalent to x*(x-1) == 0
+fn is_booleanity_gate<Fr: PrimeField>(g: &Gate<Fr>, x: Index) -> bool {
+    match (as_affine(&g.0, x), as_affine(&g.1, x), as_affine(&g.2, x)) {
+        (Some((ka, ba)), Some((kb, bb)), Some((kc, bc))) => {
+            let k2 = ka * kb;
+            let k1 = ka * bb + ba * kb - kc;
+            let k0 = ba * bb - bc;
+            !k2.is_zero() && k1 == -k2 && k0.is_zero()
+        }
+        _ => false,
+    }
+}
+
+pub fn analyze_gates<Fr: PrimeField>(
+    gates: &[Gate<Fr>],
+    num_input: usize,
+    num_aux: usize,
+    unchecked_bits: &[u32],
+) -> AnalysisReport {
+    let mut usage = vec![
+        Usage {
+            num_gates: 0,
+            last_gate: 0,
+            nonlinear: false
+        };
+        num_aux
+    ];
+    let mut checked = vec![false; num_aux];
+
+    for (n, g) in gates.iter().enumerate() {
+        let a_const = is_const_lc(&g.0);
+        let b_const = is_const_lc(&g.1);
+
+        let mut vars = g
+            .0
+            .iter()
+            .chain(g.1.iter())
+            .chain(g.2.iter())
+            .filter_map(|(_, i)| match i {
+                Index::Aux(i) => Some(*i),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        vars.sort_unstable();
+        vars.dedup();
+
+        for i in vars {
+            let index = Index::Aux(i);
+            let u = &mut usage[i as usize];
+            u.num_gates += 1;
+            u.last_gate = n;
+            u.nonlinear |= (contains(&g.0, index) && !b_const) || (contains(&g.1, index) && !a_const);
+            if !checked[i as usize] && is_booleanity_gate(g, index) {
+                checked[i as usize] = true;
+            }
+        }
+    }
+
+    let unused_aux = (0..num_aux as u32)
+        .filter(|&i| usage[i as usize].num_gates == 0)
+        .collect();
+
+    let single_linear = (0..num_aux as u32)
+        .filter(|&i| usage[i as usize].num_gates == 1 && !usage[i as usize].nonlinear)
+        .map(|i| (i, usage[i as usize].last_gate))
+        .collect();
+
+    let mut unchecked_bits = unchecked_bits
+        .iter()
+        .cloned()
+        .filter(|&i| !checked[i as usize])
+        .collect::<Vec<_>>();
+    unchecked_bits.sort_unstable();
+    unchecked_bits.dedup();
+
+    AnalysisReport {
+        num_gates: gates.len(),
+        num_input,
+        num_aux,
+        unused_aux,
+        single_linear,
+        unchecked_bits,
+    }
+}
+
+pub fn analyze_cs<Fr: PrimeField, L: AbstractLC<Fr>>(cs: &BuildCS<Fr, L>) -> AnalysisReport {
+    analyze_gates(&cs.gates, cs.num_input, cs.num_aux, &cs.unchecked_bits)
+}
+
+// Build the circuit the same way as setup does and analyze it
+pub fn analyze<Fr: PrimeField, Pub: Signal<BuildCS<Fr>>, Sec: Signal<BuildCS<Fr>>, C: Fn(Pub, Sec)>(
+    circuit: C,
+) -> AnalysisReport {
+    let ref rcs = BuildCS::rc_new();
+    let signal_pub = Pub::alloc(rcs, None);
+    signal_pub.inputize();
+    let signal_sec = Sec::alloc(rcs, None);
+
+    circuit(signal_pub, signal_sec);
+
+    let cs = rcs.borrow();
+    analyze_cs(&cs)
+}
@@ -3,7 +3,7 @@ use crate::{
         cs::{CS, RCS},
         num::CNum,
     },
-    core::signal::Signal,
+    core::signal::{read_input, Signal},
     ff_uint::{Num},
 };
 use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
@@ -13,12 +13,13 @@ pub struct CBool<C: CS>(CNum<C>);
 
 impl<C: CS> CBool<C> {
     pub fn new_unchecked(n: &CNum<C>) -> Self {
+        n.get_cs().borrow_mut().unchecked_bit_hint(&n.lc);
         CBool(n.clone())
     }
 
     pub fn new(n: &CNum<C>) -> Self {
         n.assert_bit();
-        Self::new_unchecked(n)
+        CBool(n.clone())
     }
 
     pub fn to_num(&self) -> CNum<C> {
@@ -58,7 +59,7 @@ impl<C: CS> Signal<C> for CBool<C> {
     }
 
     fn from_const(cs: &RCS<C>, value: &Self::Value) -> Self {
-        Self::new_unchecked(&CNum::from_const(cs, &(*value).into()))
+        CBool(CNum::from_const(cs, &(*value).into()))
     }
 
     fn get_cs(&self) -> &RCS<C> {
@@ -79,9 +80,7 @@ impl<C: CS> Signal<C> for CBool<C> {
     }
 
     fn switch(&self, bit: &CBool<C>, if_else: &Self) -> Self {
-        self.to_num()
-            .switch(bit, &if_else.to_num())
-            .to_bool_unchecked()
+        CBool(self.to_num().switch(bit, &if_else.to_num()))
     }
 
     fn assert_eq(&self, other: &Self) {
@@ -101,13 +100,25 @@ impl<C: CS> Signal<C> for CBool<C> {
         );
         signal
     }
+
+    fn write_inputs(value: &Self::Value, inputs: &mut Vec<Num<C::Fr>>) {
+        inputs.push((*value).into());
+    }
+
+    fn read_inputs(inputs: &mut &[Num<C::Fr>]) -> Option<Self::Value> {
+        match read_input(inputs)? {
+            v if v == Num::ZERO => Some(false),
+            v if v == Num::ONE => Some(true),
+            _ => None,
+        }
+    }
 }
 
 impl<C: CS> Not for CBool<C> {
     type Output = Self;
 
     fn not(self) -> Self::Output {
-        (Num::ONE - self.to_num()).to_bool_unchecked()
+        CBool(Num::ONE - self.to_num())
     }
 }
 
@@ -117,7 +128,7 @@ forward_unop_ex!(impl<C: CS> Not for CBool<C>, not);
 impl<'l, C: CS> BitAndAssign<&'l CBool<C>> for CBool<C> {
     #[inline]
     fn bitand_assign(&mut self, other: &'l CBool<C>) {
-        *self = (self.to_num() * other.to_num()).to_bool_unchecked()
+        *self = CBool(self.to_num() * other.to_num())
     }
 }
 
@@ -0,0 +1,333 @@
+// Circom binary formats, see https://github.com/iden3/r1csfile/blob/master/doc/r1cs_bin_format.md
+//
+// Wires are numbered as in circom: wire 0 is the constant one, then public inputs, then aux variables.
+
+use std::{
+    io::{Error, ErrorKind, Read, Write},
+    ops::Deref,
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    circuit::{cs::BuildCS, gates::Gate, lc::{AbstractLC, Index}, witness::Witness},
+    ff_uint::{Num, NumRepr, PrimeField, PrimeFieldParams, Uint},
+};
+
+pub const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+pub const R1CS_VERSION: u32 = 1;
+pub const WTNS_MAGIC: &[u8; 4] = b"wtns";
+pub const WTNS_VERSION: u32 = 2;
+
+const SECTION_HEADER: u32 = 1;
+const SECTION_CONSTRAINTS: u32 = 2;
+const SECTION_WIRE2LABEL: u32 = 3;
+const SECTION_WITNESS: u32 = 2;
+
+// Constraint system read from the .r1cs file
+#[derive(Clone, Debug)]
+pub struct R1cs<Fr: PrimeField> {
+    pub num_input: usize,
+    pub num_aux: usize,
+    pub gates: Vec<Gate<Fr>>,
+}
+
+impl<Fr: PrimeField> R1cs<Fr> {
+    // all gates are placed into the root scope
+    pub fn into_build_cs(self) -> BuildCS<Fr> {
+        let mut cs = BuildCS::new();
+        cs.num_input = self.num_input;
+        cs.num_aux = self.num_aux;
+        cs.gate_scopes = vec![0; self.gates.len()];
+        cs.aux_scopes = vec![0; self.num_aux];
+        cs.gates = self.gates;
+        cs
+    }
+
+    // split circom witness into input and aux values
+    pub fn split_witness(&self, witness: &[Num<Fr>]) -> std::io::Result<Witness<Fr>> {
+        split_witness(witness, self.num_input, self.num_aux)
+    }
+}
+
+// size of the field element in bytes
+pub fn field_size<Fr: PrimeField>() -> usize {
+    <Fr as PrimeFieldParams>::Inner::NUM_WORDS * <Fr as PrimeFieldParams>::Inner::WORD_BITS / 8
+}
+
+pub fn wire_id(index: Index, num_input: usize) -> u32 {
+    match index {
+        Index::Input(i) => i,
+        Index::Aux(i) => num_input as u32 + i,
+    }
+}
+
+pub fn wire_index(wire: u32, num_input: usize) -> Index {
+    if (wire as usize) < num_input {
+        Index::Input(wire)
+    } else {
+        Index::Aux(wire - num_input as u32)
+    }
+}
+
+pub fn split_witness<Fr: PrimeField>(
+    witness: &[Num<Fr>],
+    num_input: usize,
+    num_aux: usize,
+) -> std::io::Result<Witness<Fr>> {
+    if witness.len() != num_input + num_aux {
+        return Err(Error::new(ErrorKind::InvalidData, "wrong number of witness values"));
+    }
+    // the lengths are equal, so the witness is not empty if there are inputs
+    if num_input == 0 || witness[0] != Num::ONE {
+        return Err(Error::new(ErrorKind::InvalidData, "first witness value should be one"));
+    }
+    Ok(Witness {
+        values_input: witness[..num_input].to_vec(),
+        values_aux: witness[num_input..].to_vec(),
+    })
+}
+
+fn write_num<Fr: PrimeField, W: Write>(writer: &mut W, n: Num<Fr>) -> std::io::Result<()> {
+    writer.write_all(&n.to_uint().0.to_little_endian())
+}
+
+fn write_section<W: Write>(writer: &mut W, section_type: u32, data: &[u8]) -> std::io::Result<()> {
+    writer.write_u32::<LittleEndian>(section_type)?;
+    writer.write_u64::<LittleEndian>(data.len() as u64)?;
+    writer.write_all(data)
+}
+
+fn write_lc<Fr: PrimeField, W: Write>(
+    writer: &mut W,
+    lc: &[(Num<Fr>, Index)],
+    num_input: usize,
+) -> std::io::Result<()> {
+    writer.write_u32::<LittleEndian>(lc.len() as u32)?;
+    for (k, i) in lc.iter() {
+        writer.write_u32::<LittleEndian>(wire_id(*i, num_input))?;
+        write_num(writer, *k)?;
+    }
+    Ok(())
+}
+
+pub fn write_r1cs<Fr: PrimeField, G: Deref<Target = Gate<Fr>>, I: IntoIterator<Item = G>, W: Write>(
+    writer: &mut W,
+    gates: I,
+    num_input: usize,
+    num_aux: usize,
+) -> std::io::Result<()> {
+    let num_wires = num_input + num_aux;
+
+    let mut constraints = vec![];
+    let mut num_gates = 0u32;
+    for g in gates {
+        write_lc(&mut constraints, &g.0, num_input)?;
+        write_lc(&mut constraints, &g.1, num_input)?;
+        write_lc(&mut constraints, &g.2, num_input)?;
+        num_gates += 1;
+    }
+
+    let mut header = vec![];
+    header.write_u32::<LittleEndian>(field_size::<Fr>() as u32)?;
+    header.write_all(&<Fr as PrimeFieldParams>::MODULUS.to_little_endian())?;
+    header.write_u32::<LittleEndian>(num_wires as u32)?;
+    // public outputs
+    header.write_u32::<LittleEndian>(0)?;
+    // public inputs
+    header.write_u32::<LittleEndian>(num_input as u32 - 1)?;
+    // private inputs are not distinguished from other aux variables
+    header.write_u32::<LittleEndian>(0)?;
+    header.write_u64::<LittleEndian>(num_wires as u64)?;
+    header.write_u32::<LittleEndian>(num_gates)?;
+
+    let mut wire2label = vec![];
+    for i in 0..num_wires as u64 {
+        wire2label.write_u64::<LittleEndian>(i)?;
+    }
+
+    writer.write_all(R1CS_MAGIC)?;
+    writer.write_u32::<LittleEndian>(R1CS_VERSION)?;
+    writer.write_u32::<LittleEndian>(3)?;
+    write_section(writer, SECTION_HEADER, &header)?;
+    write_section(writer, SECTION_CONSTRAINTS, &constraints)?;
+    write_section(writer, SECTION_WIRE2LABEL, &wire2label)
+}
+
+pub fn write_wtns<'a, Fr: PrimeField + 'a, I: IntoIterator<Item = &'a Num<Fr>>, W: Write>(
+    writer: &mut W,
+    values: I,
+) -> std::io::Result<()> {
+    let mut witness = vec![];
+    let mut num_witness = 0u32;
+    for v in values {
+        write_num(&mut witness, *v)?;
+        num_witness += 1;
+    }
+
+    let mut header = vec![];
+    header.write_u32::<LittleEndian>(field_size::<Fr>() as u32)?;
+    header.write_all(&<Fr as PrimeFieldParams>::MODULUS.to_little_endian())?;
+    header.write_u32::<LittleEndian>(num_witness)?;
+
+    writer.write_all(WTNS_MAGIC)?;
+    writer.write_u32::<LittleEndian>(WTNS_VERSION)?;
+    writer.write_u32::<LittleEndian>(2)?;
+    write_section(writer, SECTION_HEADER, &header)?;
+    write_section(writer, SECTION_WITNESS, &witness)
+}
+
+pub fn write_r1cs_cs<Fr: PrimeField, L: AbstractLC<Fr>, W: Write>(
+    writer: &mut W,
+    cs: &BuildCS<Fr, L>,
+) -> std::io::Result<()> {
+    write_r1cs(writer, cs.gates.iter(), cs.num_input, cs.num_aux)
+}
+
+// Symbols file with lines `label_id,wire_id,component_id,name`, the gadget scope of the allocation is used as the
+// component, the scope path is used as the component name
+pub fn write_sym<Fr: PrimeField, L: AbstractLC<Fr>, W: Write>(writer: &mut W, cs: &BuildCS<Fr, L>) -> std::io::Result<()> {
+    for i in 1..cs.num_input {
+        writeln!(writer, "{},{},0,main.input_{}", i, i, i)?;
+    }
+    for i in 0..cs.num_aux {
+        let wire = wire_id(Index::Aux(i as u32), cs.num_input);
+        let scope = cs.aux_scopes[i];
+        let path = cs.scopes.get(scope);
+        if path.is_empty() {
+            writeln!(writer, "{},{},{},main.aux_{}", wire, wire, scope, i)?;
+        } else {
+            writeln!(writer, "{},{},{},main.{}.aux_{}", wire, wire, scope, path.replace('/', "."), i)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_num<Fr: PrimeField, R: Read>(reader: &mut R) -> std::io::Result<Num<Fr>> {
+    let mut buf = vec![0; field_size::<Fr>()];
+    reader.read_exact(&mut buf)?;
+    Num::from_uint(NumRepr(Uint::from_little_endian(&buf)))
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "field element overflow"))
+}
+
+// check field element size and prime in the header
+fn read_field<Fr: PrimeField, R: Read>(reader: &mut R) -> std::io::Result<()> {
+    let n8 = reader.read_u32::<LittleEndian>()? as usize;
+    if n8 != field_size::<Fr>() {
+        return Err(Error::new(ErrorKind::InvalidData, "wrong field element size"));
+    }
+    let mut prime = vec![0; n8];
+    reader.read_exact(&mut prime)?;
+    if prime != <Fr as PrimeFieldParams>::MODULUS.to_little_endian() {
+        return Err(Error::new(ErrorKind::InvalidData, "wrong field prime"));
+    }
+    Ok(())
+}
+
+fn read_sections<R: Read>(reader: &mut R, magic: &[u8; 4], version: u32) -> std::io::Result<Vec<(u32, Vec<u8>)>> {
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf)?;
+    if &buf != magic {
+        return Err(Error::new(ErrorKind::InvalidData, "wrong magic"));
+    }
+    if reader.read_u32::<LittleEndian>()? != version {
+        return Err(Error::new(ErrorKind::InvalidData, "unsupported version"));
+    }
+    let num_sections = reader.read_u32::<LittleEndian>()?;
+    let mut sections = vec![];
+    for _ in 0..num_sections {
+        let section_type = reader.read_u32::<LittleEndian>()?;
+        let size = reader.read_u64::<LittleEndian>()?;
+        let mut data = vec![];
+        reader.take(size).read_to_end(&mut data)?;
+        if data.len() as u64 != size {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "section is truncated"));
+        }
+        sections.push((section_type, data));
+    }
+    Ok(sections)
+}
+
+fn find_section(sections: &[(u32, Vec<u8>)], section_type: u32) -> std::io::Result<&[u8]> {
+    sections
+        .iter()
+        .find(|(t, _)| *t == section_type)
+        .map(|(_, data)| &data[..])
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "section not found"))
+}
+
+// The counts are not trusted, so they are checked against the remaining data before the allocation
+fn check_count(data: &[u8], count: usize, record_size: usize) -> std::io::Result<()> {
+    if count > data.len() / record_size {
+        return Err(Error::new(ErrorKind::InvalidData, "count exceeds the section size"));
+    }
+    Ok(())
+}
+
+fn read_lc<Fr: PrimeField>(
+    reader: &mut &[u8],
+    num_input: usize,
+    num_wires: usize,
+) -> std::io::Result<Vec<(Num<Fr>, Index)>> {
+    let n = reader.read_u32::<LittleEndian>()? as usize;
+    check_count(reader, n, 4 + field_size::<Fr>())?;
+    let mut lc = Vec::with_capacity(n);
+    for _ in 0..n {
+        let wire = reader.read_u32::<LittleEndian>()?;
+        if wire as usize >= num_wires {
+            return Err(Error::new(ErrorKind::InvalidData, "wire id overflow"));
+        }
+        let k = read_num(reader)?;
+        lc.push((k, wire_index(wire, num_input)));
+    }
+    Ok(lc)
+}
+
+// Public outputs and public inputs of circom circuit are both mapped to the inputs
+pub fn read_r1cs<Fr: PrimeField, R: Read>(reader: &mut R) -> std::io::Result<R1cs<Fr>> {
+    let sections = read_sections(reader, R1CS_MAGIC, R1CS_VERSION)?;
+
+    let header = &mut find_section(&sections, SECTION_HEADER)?;
+    read_field::<Fr, _>(header)?;
+    let num_wires = header.read_u32::<LittleEndian>()? as usize;
+    let num_pub_out = header.read_u32::<LittleEndian>()? as usize;
+    let num_pub_in = header.read_u32::<LittleEndian>()? as usize;
+    let _num_prv_in = header.read_u32::<LittleEndian>()?;
+    let _num_labels = header.read_u64::<LittleEndian>()?;
+    let num_gates = header.read_u32::<LittleEndian>()? as usize;
+
+    let num_input = 1 + num_pub_out + num_pub_in;
+    if num_input > num_wires {
+        return Err(Error::new(ErrorKind::InvalidData, "wrong number of wires"));
+    }
+
+    let constraints = &mut find_section(&sections, SECTION_CONSTRAINTS)?;
+    // each gate has at least 3 counts of the linear combinations
+    check_count(constraints, num_gates, 12)?;
+    let mut gates = Vec::with_capacity(num_gates);
+    for _ in 0..num_gates {
+        let a = read_lc(constraints, num_input, num_wires)?;
+        let b = read_lc(constraints, num_input, num_wires)?;
+        let c = read_lc(constraints, num_input, num_wires)?;
+        gates.push(Gate(a, b, c));
+    }
+
+    Ok(R1cs {
+        num_input,
+        num_aux: num_wires - num_input,
+        gates,
+    })
+}
+
+// Values of all wires, the first one is the constant one
+pub fn read_wtns<Fr: PrimeField, R: Read>(reader: &mut R) -> std::io::Result<Vec<Num<Fr>>> {
+    let sections = read_sections(reader, WTNS_MAGIC, WTNS_VERSION)?;
+
+    let header = &mut find_section(&sections, SECTION_HEADER)?;
+    read_field::<Fr, _>(header)?;
+    let num_witness = header.read_u32::<LittleEndian>()? as usize;
+
+    let data = &mut find_section(&sections, SECTION_WITNESS)?;
+    check_count(data, num_witness, field_size::<Fr>())?;
+    (0..num_witness).map(|_| read_num(data)).collect()
+}
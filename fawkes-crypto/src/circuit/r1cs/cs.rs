@@ -1,18 +1,80 @@
 use crate::{
     circuit::{
         num::CNum,
-        lc::{LC, Index, AbstractLC, ZeroLC}
+        lc::{Index, AbstractLC, ZeroLC}
     },
     core::signal::Signal,
     ff_uint::{Num, PrimeField}
 };
 
-use std::{cell::RefCell, marker::PhantomData, rc::Rc};
+use std::marker::PhantomData;
+#[cfg(not(feature = "multithread"))]
+use std::{cell::RefCell, rc::Rc};
+#[cfg(not(feature = "multithread"))]
+use super::lc::LC;
+#[cfg(feature = "multithread")]
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+#[cfg(feature = "multithread")]
+use super::lc::VecLC;
 use bit_vec::BitVec;
 
+#[cfg(not(feature = "multithread"))]
 pub type RCS<C> = Rc<RefCell<C>>;
 
-use super::gates::{Gate, self, GateSource, GateWrapper};
+// With multithread feature the constraint systems and the signals are Send and Sync (if the LC is), so circuits
+// could be built and proved on any thread
+#[cfg(feature = "multithread")]
+pub type RCS<C> = Arc<SyncCell<C>>;
+
+// LC of the constraint systems by default, the linked list is not Send, so the vector is used with multithread feature
+#[cfg(not(feature = "multithread"))]
+pub type DefaultLC<Fr> = LC<Fr>;
+
+#[cfg(feature = "multithread")]
+pub type DefaultLC<Fr> = VecLC<Fr>;
+
+#[cfg(not(feature = "multithread"))]
+pub fn new_rcs<C>(cs: C) -> RCS<C> {
+    Rc::new(RefCell::new(cs))
+}
+
+#[cfg(feature = "multithread")]
+pub fn new_rcs<C>(cs: C) -> RCS<C> {
+    Arc::new(SyncCell::new(cs))
+}
+
+// RefCell-like interface over RwLock. As RefCell, it panics if the cs is already borrowed, so the reentrant borrow
+// is not a deadlock. The constraint system should be used by one thread at a time.
+#[cfg(feature = "multithread")]
+#[derive(Debug)]
+pub struct SyncCell<C>(RwLock<C>);
+
+#[cfg(feature = "multithread")]
+impl<C> SyncCell<C> {
+    pub fn new(cs: C) -> Self {
+        SyncCell(RwLock::new(cs))
+    }
+
+    pub fn borrow(&self) -> RwLockReadGuard<'_, C> {
+        match self.0.try_read() {
+            Ok(cs) => cs,
+            Err(TryLockError::WouldBlock) => panic!("cs is already mutably borrowed"),
+            Err(TryLockError::Poisoned(_)) => panic!("cs lock is poisoned"),
+        }
+    }
+
+    pub fn borrow_mut(&self) -> RwLockWriteGuard<'_, C> {
+        match self.0.try_write() {
+            Ok(cs) => cs,
+            Err(TryLockError::WouldBlock) => panic!("cs is already borrowed"),
+            Err(TryLockError::Poisoned(_)) => panic!("cs lock is poisoned"),
+        }
+    }
+}
+
+use super::gates::{Gate, self, GateSource, GateWrapper, UnsatisfiedGate};
+use super::profile::Profiler;
+use super::scope::ScopeNames;
 
 
 pub trait CS: Clone {
@@ -36,52 +98,104 @@ pub trait CS: Clone {
     }
 
     fn const_tracker_after(&mut self, _:bool) {}
+
+    // called for signals converted to booleans without booleanity check
+    fn unchecked_bit_hint(&mut self, _:&Self::LC) {}
+
+    // enter the named gadget scope
+    fn push_scope(&mut self, _:&str) {}
+
+    // leave the current gadget scope
+    fn pop_scope(&mut self) {}
+
+    // full path of the gadget scope, which emitted the gate
+    fn get_gate_scope(&self, _:usize) -> Option<&str> {
+        None
+    }
 }
 
 
 #[derive(Clone, Debug)]
-pub struct DebugCS<Fr: PrimeField> {
+pub struct DebugCS<Fr: PrimeField, L: AbstractLC<Fr> = DefaultLC<Fr>> {
     pub num_input:usize,
     pub num_aux:usize,
     pub num_gates: usize,
-    pub phantom: PhantomData<Fr>
+    pub profiler: Profiler,
+    pub phantom: PhantomData<(Fr, L)>
 }
 
 impl<Fr: PrimeField> DebugCS<Fr> {
     pub fn new() -> Self {
+        Self::new_with_lc()
+    }
+
+    pub fn rc_new() -> RCS<Self> {
+        Self::rc_new_with_lc()
+    }
+}
+
+impl<Fr: PrimeField, L: AbstractLC<Fr>> DebugCS<Fr, L> {
+    pub fn new_with_lc() -> Self {
         Self {
             num_input: 1,
             num_aux: 0,
             num_gates: 0,
+            profiler: Profiler::new(),
             phantom: PhantomData
         }
     }
 
-    pub fn rc_new() -> RCS<Self> {
-        Rc::new(RefCell::new(Self::new()))
+    pub fn rc_new_with_lc() -> RCS<Self> {
+        new_rcs(Self::new_with_lc())
     }
 }
 
+// LC implementation could be selected with the second type parameter, e.g. BuildCS<Fr, VecLC<Fr>>
 #[derive(Clone, Debug)]
-pub struct BuildCS<Fr: PrimeField> {
+pub struct BuildCS<Fr: PrimeField, L: AbstractLC<Fr> = DefaultLC<Fr>> {
     pub num_input:usize,
     pub num_aux:usize,
     pub gates: Vec<Gate<Fr>>,
-    pub const_tracker: BitVec
+    pub const_tracker: BitVec,
+    pub unchecked_bits: Vec<u32>,
+    pub gate_scopes: Vec<usize>,
+    pub aux_scopes: Vec<usize>,
+    pub scopes: ScopeNames,
+    pub phantom: PhantomData<L>
 }
 
 impl<Fr: PrimeField> BuildCS<Fr> {
     pub fn new() -> Self {
+        Self::new_with_lc()
+    }
+
+    pub fn rc_new() -> RCS<Self> {
+        Self::rc_new_with_lc()
+    }
+}
+
+impl<Fr: PrimeField, L: AbstractLC<Fr>> BuildCS<Fr, L> {
+    pub fn new_with_lc() -> Self {
         Self {
             num_input: 1,
             num_aux: 0,
             gates: vec![],
-            const_tracker: BitVec::new()
+            const_tracker: BitVec::new(),
+            unchecked_bits: vec![],
+            gate_scopes: vec![],
+            aux_scopes: vec![],
+            scopes: ScopeNames::new(),
+            phantom: PhantomData
         }
     }
 
-    pub fn rc_new() -> RCS<Self> {
-        Rc::new(RefCell::new(Self::new()))
+    pub fn rc_new_with_lc() -> RCS<Self> {
+        new_rcs(Self::new_with_lc())
+    }
+
+    fn push_gate(&mut self, gate: Gate<Fr>) {
+        self.gates.push(gate);
+        self.gate_scopes.push(self.scopes.current());
     }
 }
 
@@ -92,7 +206,10 @@ pub struct WitnessCS<'a, Fr: PrimeField> {
     pub num_gates: usize,
     pub gates: GateSource<'a, Fr>,
     pub const_tracker: &'a BitVec,
-    pub const_tracker_index: usize
+    pub const_tracker_index: usize,
+    // the scopes of the gates are recorded only after record_scopes, as they are needed only for checked proving
+    pub scopes: Option<ScopeNames>,
+    pub gate_scopes: Vec<usize>
 }
 
 impl<'a, Fr: PrimeField> WitnessCS<'a, Fr> {
@@ -103,19 +220,71 @@ impl<'a, Fr: PrimeField> WitnessCS<'a, Fr> {
             num_gates,
             gates,
             const_tracker,
-            const_tracker_index: 0
+            const_tracker_index: 0,
+            scopes: None,
+            gate_scopes: vec![]
         }
     }
 
     pub fn rc_new(num_gates:usize, gates: GateSource<'a, Fr>, const_tracker: &'a BitVec) -> RCS<Self> {
-        Rc::new(RefCell::new(Self::new(num_gates, gates, const_tracker)))
+        new_rcs(Self::new(num_gates, gates, const_tracker))
+    }
+
+    pub fn record_scopes(&mut self) {
+        self.scopes = Some(ScopeNames::new());
+    }
+
+    // gates are enforced in the same order as in BuildCS, so the gate index is the index in the setup gates
+    fn push_gate_scope(&mut self) {
+        if let Some(scopes) = &self.scopes {
+            let scope = scopes.current();
+            self.gate_scopes.push(scope);
+        }
     }
 }
 
+// Records both gates and values, so the assignment could be checked gate by gate
+#[derive(Clone, Debug)]
+pub struct CheckCS<Fr: PrimeField> {
+    pub values_input: Vec<Num<Fr>>,
+    pub values_aux: Vec<Num<Fr>>,
+    pub gates: Vec<Gate<Fr>>,
+    pub gate_scopes: Vec<usize>,
+    pub scopes: ScopeNames,
+}
 
-impl<Fr: PrimeField>  CS for DebugCS<Fr> {
+impl<Fr: PrimeField> CheckCS<Fr> {
+    pub fn new() -> Self {
+        Self {
+            values_input: vec![Num::ONE],
+            values_aux: vec![],
+            gates: vec![],
+            gate_scopes: vec![],
+            scopes: ScopeNames::new(),
+        }
+    }
+
+    pub fn rc_new() -> RCS<Self> {
+        new_rcs(Self::new())
+    }
+
+    fn push_gate(&mut self, gate: Gate<Fr>) {
+        self.gates.push(gate);
+        self.gate_scopes.push(self.scopes.current());
+    }
+
+    // return the first gate, which is not satisfied by the assignment
+    pub fn check(&self) -> Result<(), UnsatisfiedGate<Fr>> {
+        gates::check_gates(self.gates.iter(), &self.values_input, &self.values_aux).map_err(|mut e| {
+            e.scope = Some(self.scopes.get(self.gate_scopes[e.index]).to_string());
+            e
+        })
+    }
+}
+
+impl<Fr: PrimeField, L: AbstractLC<Fr>> CS for DebugCS<Fr, L> {
     type Fr = Fr;
-    type LC = LC<Fr>;
+    type LC = L;
     type GateIterator<'a> = core::iter::Empty<GateWrapper<'a, Self::Fr>> where Self: 'a;
 
     fn num_gates(&self) -> usize {
@@ -141,6 +310,7 @@ impl<Fr: PrimeField>  CS for DebugCS<Fr> {
     fn enforce(a: &CNum<Self>, b: &CNum<Self>, c: &CNum<Self>) {
         let mut rcs = a.get_cs().borrow_mut();
         rcs.num_gates+=1;
+        rcs.profiler.add_gate(a.lc.capacity() + b.lc.capacity() + c.lc.capacity());
 
         match (a.value, b.value, c.value) {
             (Some(a), Some(b), Some(c)) => {
@@ -155,19 +325,29 @@ impl<Fr: PrimeField>  CS for DebugCS<Fr> {
         let mut rcs = n.get_cs().borrow_mut();
         rcs.num_gates+=1;
         rcs.num_input+=1;
+        rcs.profiler.add_gate(n.lc.capacity() + 2);
     }
 
     fn alloc(cs: &RCS<Self>, value: Option<&Num<Self::Fr>>) -> CNum<Self> {
         let mut rcs = cs.borrow_mut();
         let v = rcs.num_aux as u32;
         rcs.num_aux+=1;
+        rcs.profiler.add_aux();
         CNum {
             value: value.cloned(),
-            lc: LC::from_index(Index::Aux(v)),
+            lc: L::from_index(Index::Aux(v)),
             cs: cs.clone(),
         }
     }
 
+    fn push_scope(&mut self, name:&str) {
+        self.profiler.push(name);
+    }
+
+    fn pop_scope(&mut self) {
+        self.profiler.pop();
+    }
+
 }
 
 impl<'a, Fr: PrimeField + 'a> CS for WitnessCS<'a, Fr> {
@@ -197,12 +377,14 @@ impl<'a, Fr: PrimeField + 'a> CS for WitnessCS<'a, Fr> {
         gates::GateIterator::new(&self.gates)   
     }
 
-    fn enforce(_: &CNum<Self>, _: &CNum<Self>, _: &CNum<Self>) {
+    fn enforce(a: &CNum<Self>, _: &CNum<Self>, _: &CNum<Self>) {
+        a.get_cs().borrow_mut().push_gate_scope();
     }
 
     fn inputize(n: &CNum<Self>) {
         let mut rcs = n.get_cs().borrow_mut();
         rcs.values_input.push(n.get_value().expect("value is empty"));
+        rcs.push_gate_scope();
     }
 
     fn alloc(cs: &RCS<Self>, value: Option<&Num<Self::Fr>>) -> CNum<Self> {
@@ -221,12 +403,28 @@ impl<'a, Fr: PrimeField + 'a> CS for WitnessCS<'a, Fr> {
         Some(self.const_tracker[i])
     }
 
+    fn push_scope(&mut self, name:&str) {
+        if let Some(scopes) = &mut self.scopes {
+            scopes.push(name);
+        }
+    }
+
+    fn pop_scope(&mut self) {
+        if let Some(scopes) = &mut self.scopes {
+            scopes.pop();
+        }
+    }
+
+    fn get_gate_scope(&self, i:usize) -> Option<&str> {
+        let scopes = self.scopes.as_ref()?;
+        self.gate_scopes.get(i).map(|&id| scopes.get(id))
+    }
 }
 
 
-impl<Fr: PrimeField> CS for BuildCS<Fr> {
+impl<Fr: PrimeField, L: AbstractLC<Fr>> CS for BuildCS<Fr, L> {
     type Fr = Fr;
-    type LC = LC<Fr>;
+    type LC = L;
     type GateIterator<'a> = gates::GateIterator<'a, Fr> where Self: 'a;
 
     fn num_gates(&self) -> usize {
@@ -251,14 +449,14 @@ impl<Fr: PrimeField> CS for BuildCS<Fr> {
     // a*b === c
     fn enforce(a: &CNum<Self>, b: &CNum<Self>, c: &CNum<Self>) {
         let mut rcs = a.get_cs().borrow_mut();
-        rcs.gates.push(Gate(a.lc.to_vec(), b.lc.to_vec(), c.lc.to_vec()))
+        rcs.push_gate(Gate(a.lc.to_vec(), b.lc.to_vec(), c.lc.to_vec()))
     }
 
     fn inputize(n: &CNum<Self>) {
         let mut rcs = n.get_cs().borrow_mut();
         let v = rcs.num_input as u32;
         rcs.num_input+=1;
-        rcs.gates.push(Gate(
+        rcs.push_gate(Gate(
             n.lc.to_vec(),
             vec![(Num::ONE, Index::Input(0))],
             vec![(Num::ONE, Index::Input(v))],
@@ -269,9 +467,11 @@ impl<Fr: PrimeField> CS for BuildCS<Fr> {
         let mut rcs = cs.borrow_mut();
         let v = rcs.num_aux as u32;
         rcs.num_aux+=1;
+        let scope = rcs.scopes.current();
+        rcs.aux_scopes.push(scope);
         CNum {
             value: None,
-            lc: LC::from_index(Index::Aux(v)),
+            lc: L::from_index(Index::Aux(v)),
             cs: cs.clone(),
         }
     }
@@ -279,4 +479,92 @@ impl<Fr: PrimeField> CS for BuildCS<Fr> {
     fn const_tracker_after(&mut self, v:bool) {
         self.const_tracker.push(v);
     }
+
+    fn unchecked_bit_hint(&mut self, lc:&Self::LC) {
+        if let [(k, Index::Aux(i))] = lc.to_vec()[..] {
+            if k == Num::ONE {
+                self.unchecked_bits.push(i);
+            }
+        }
+    }
+
+    fn push_scope(&mut self, name:&str) {
+        self.scopes.push(name);
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn get_gate_scope(&self, i:usize) -> Option<&str> {
+        Some(self.scopes.get(self.gate_scopes[i]))
+    }
+}
+
+impl<Fr: PrimeField> CS for CheckCS<Fr> {
+    type Fr = Fr;
+    type LC = DefaultLC<Fr>;
+    type GateIterator<'a> = gates::GateIterator<'a, Fr> where Self: 'a;
+
+    fn num_gates(&self) -> usize {
+        self.gates.len()
+    }
+
+    fn num_input(&self) -> usize {
+        self.values_input.len()
+    }
+    fn num_aux(&self) -> usize {
+        self.values_aux.len()
+    }
+
+    fn get_value(&self, index:Index) -> Option<Num<Fr>> {
+        match index {
+            Index::Input(i) => Some(self.values_input[i as usize]),
+            Index::Aux(i) => Some(self.values_aux[i as usize]),
+        }
+    }
+
+    fn get_gate_iterator(&self) -> Self::GateIterator<'_> {
+        gates::GateIterator::new(&GateSource::Precomputed(&self.gates))
+    }
+
+    // a*b === c
+    fn enforce(a: &CNum<Self>, b: &CNum<Self>, c: &CNum<Self>) {
+        let mut rcs = a.get_cs().borrow_mut();
+        rcs.push_gate(Gate(a.lc.to_vec(), b.lc.to_vec(), c.lc.to_vec()))
+    }
+
+    fn inputize(n: &CNum<Self>) {
+        let mut rcs = n.get_cs().borrow_mut();
+        let v = rcs.values_input.len() as u32;
+        rcs.values_input.push(n.get_value().expect("value is empty"));
+        rcs.push_gate(Gate(
+            n.lc.to_vec(),
+            vec![(Num::ONE, Index::Input(0))],
+            vec![(Num::ONE, Index::Input(v))],
+        ));
+    }
+
+    fn alloc(cs: &RCS<Self>, value: Option<&Num<Self::Fr>>) -> CNum<Self> {
+        let mut rcs = cs.borrow_mut();
+        let v = rcs.values_aux.len() as u32;
+        rcs.values_aux.push(value.cloned().expect("value is empty"));
+        CNum {
+            value: value.cloned(),
+            lc: DefaultLC::from_index(Index::Aux(v)),
+            cs: cs.clone(),
+        }
+    }
+
+    fn push_scope(&mut self, name:&str) {
+        self.scopes.push(name);
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn get_gate_scope(&self, i:usize) -> Option<&str> {
+        Some(self.scopes.get(self.gate_scopes[i]))
+    }
 }
@@ -80,6 +80,64 @@ impl<'a, Fr: PrimeField> Iterator for GateIterator<'a, Fr> {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct UnsatisfiedGate<Fr: PrimeField> {
+    pub index: usize,
+    pub a: Num<Fr>,
+    pub b: Num<Fr>,
+    pub c: Num<Fr>,
+    pub scope: Option<String>,
+}
+
+impl<Fr: PrimeField> std::fmt::Display for UnsatisfiedGate<Fr> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Not satisfied constraint {}", self.index)?;
+        if let Some(scope) = &self.scope {
+            write!(f, " at {}", scope)?;
+        }
+        write!(f, ": {} * {} != {}", self.a, self.b, self.c)
+    }
+}
+
+impl<Fr: PrimeField> std::error::Error for UnsatisfiedGate<Fr> {}
+
+pub fn evaluate_lc<Fr: PrimeField>(
+    lc: &[(Num<Fr>, Index)],
+    values_input: &[Num<Fr>],
+    values_aux: &[Num<Fr>],
+) -> Num<Fr> {
+    lc.iter().fold(Num::ZERO, |acc, (k, i)| {
+        let v = match i {
+            Index::Input(i) => values_input[*i as usize],
+            Index::Aux(i) => values_aux[*i as usize],
+        };
+        acc + *k * v
+    })
+}
+
+// return the first gate, which is not satisfied by the assignment
+pub fn check_gates<Fr: PrimeField, G: Deref<Target = Gate<Fr>>, I: IntoIterator<Item = G>>(
+    gates: I,
+    values_input: &[Num<Fr>],
+    values_aux: &[Num<Fr>],
+) -> Result<(), UnsatisfiedGate<Fr>> {
+    for (index, g) in gates.into_iter().enumerate() {
+        let a = evaluate_lc(&g.0, values_input, values_aux);
+        let b = evaluate_lc(&g.1, values_input, values_aux);
+        let c = evaluate_lc(&g.2, values_input, values_aux);
+        if a * b != c {
+            return Err(UnsatisfiedGate {
+                index,
+                a,
+                b,
+                c,
+                scope: None,
+            });
+        }
+    }
+    Ok(())
+}
+
 pub fn evaluate_gates_memory_size<Fr: PrimeField>(
     num_gates: usize,
     bytes: &[u8],
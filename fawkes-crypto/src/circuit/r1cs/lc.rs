@@ -141,7 +141,128 @@ impl<Fr:PrimeField> AbstractLC<Fr> for LC<Fr> {
 
 
 
-#[derive(PartialEq, Copy, Clone, Debug)]
+// Terms are sorted by index, so add and sub are merges of two sorted vectors
+#[derive(Clone, Debug)]
+pub struct VecLC<Fr:PrimeField>(pub Vec<(Num<Fr>, Index)>);
+
+impl<Fr:PrimeField> VecLC<Fr> {
+    #[inline]
+    fn merge_assign<F: Fn(Num<Fr>) -> Num<Fr>>(&mut self, other: &VecLC<Fr>, f: F) {
+        // single term is usually added to the long lc, so it is cheaper to insert it in place
+        if other.0.len() == 1 {
+            let (v, k) = other.0[0];
+            match self.0.binary_search_by(|e| e.1.cmp(&k)) {
+                Ok(i) => {
+                    self.0[i].0 += f(v);
+                    if self.0[i].0.is_zero() {
+                        self.0.remove(i);
+                    }
+                }
+                Err(i) => self.0.insert(i, (f(v), k)),
+            }
+            return;
+        }
+
+        let mut res = Vec::with_capacity(self.0.len() + other.0.len());
+        let mut a = self.0.iter().peekable();
+        let mut b = other.0.iter().peekable();
+        loop {
+            match (a.peek(), b.peek()) {
+                (Some(&&x), Some(&&y)) => match x.1.cmp(&y.1) {
+                    core::cmp::Ordering::Less => {
+                        res.push(x);
+                        a.next();
+                    }
+                    core::cmp::Ordering::Greater => {
+                        res.push((f(y.0), y.1));
+                        b.next();
+                    }
+                    core::cmp::Ordering::Equal => {
+                        let v = x.0 + f(y.0);
+                        if !v.is_zero() {
+                            res.push((v, x.1));
+                        }
+                        a.next();
+                        b.next();
+                    }
+                },
+                (Some(&&x), None) => {
+                    res.push(x);
+                    a.next();
+                }
+                (None, Some(&&y)) => {
+                    res.push((f(y.0), y.1));
+                    b.next();
+                }
+                (None, None) => break,
+            }
+        }
+        self.0 = res;
+    }
+}
+
+impl<Fr:PrimeField> AbstractLC<Fr> for VecLC<Fr> {
+    fn to_vec(&self) -> Vec<(Num<Fr>, Index)> {
+        self.0.clone()
+    }
+
+    fn new() -> Self {
+        VecLC(Vec::new())
+    }
+
+    fn from_index(index:Index) -> Self {
+        Self::from_parts(Num::ONE, index)
+    }
+
+    fn from_parts(value:Num<Fr>, index:Index) -> Self {
+        VecLC(vec![(value, index)])
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn as_const(&self) -> Option<Num<Fr>> {
+        match self.0[..] {
+            [] => Some(Num::ZERO),
+            [(v, Index::Input(0))] => Some(v),
+            _ => None,
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    fn add_assign(&mut self, other: &VecLC<Fr>) {
+        self.merge_assign(other, |v| v)
+    }
+
+    #[inline]
+    fn sub_assign(&mut self, other: &VecLC<Fr>) {
+        self.merge_assign(other, |v| -v)
+    }
+
+    #[inline]
+    fn mul_assign(&mut self, other: &Num<Fr>) {
+        if other.is_zero() {
+            self.0.clear();
+        } else {
+            for (v, _) in self.0.iter_mut() {
+                *v *= other;
+            }
+        }
+    }
+
+    #[inline]
+    fn neg(&self) -> Self {
+        VecLC(self.0.iter().map(|&(v, k)| (-v, k)).collect())
+    }
+}
+
+
+#[derive(PartialEq, Copy, Clone, Debug, Hash)]
 #[cfg_attr(feature = "borsh_support", derive(BorshSerialize, BorshDeserialize))]
 pub enum Index {
     Input(u32),
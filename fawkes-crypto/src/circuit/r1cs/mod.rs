@@ -2,4 +2,10 @@ pub mod bool;
 pub mod cs;
 pub mod num;
 pub mod lc;
-pub mod gates;
\ No newline at end of file
+pub mod gates;
+pub mod analysis;
+pub mod scope;
+pub mod profile;
+pub mod circom;
+pub mod witness;
+pub mod optimizer;
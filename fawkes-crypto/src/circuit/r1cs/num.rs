@@ -5,7 +5,7 @@ use crate::{
         lc::{Index},
         bitify::c_into_bits_le_strict
     },
-    core::signal::Signal,
+    core::signal::{read_input, Signal},
     ff_uint::{Num},
 };
 
@@ -177,6 +177,14 @@ impl<C: CS> Signal<C> for CNum<C> {
     fn is_eq(&self, other: &Self) -> CBool<C> {
         (self - other).is_zero()
     }
+
+    fn write_inputs(value: &Self::Value, inputs: &mut Vec<Num<C::Fr>>) {
+        inputs.push(*value);
+    }
+
+    fn read_inputs(inputs: &mut &[Num<C::Fr>]) -> Option<Self::Value> {
+        read_input(inputs)
+    }
 }
 
 impl<C: CS> CNum<C> {
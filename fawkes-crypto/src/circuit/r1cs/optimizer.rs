@@ -0,0 +1,230 @@
+use std::collections::HashSet;
+
+use crate::{
+    circuit::{
+        cs::BuildCS,
+        gates::Gate,
+        lc::{AbstractLC, Index, VecLC},
+    },
+    ff_uint::{Num, NumRepr, PrimeField},
+};
+
+// Optimization of the constraint system before setup:
+// - aux variables defined by linear gates (e.g. `a * 1 = b` emitted by assert_eq) are substituted into other gates
+//   and the defining gates are removed
+// - aux variables are renumbered, so the eliminated variables are skipped
+// - duplicated gates are removed
+//
+// The witness for the optimized constraint system is obtained from the witness for the original one with map_aux.
+
+// sorted lc without duplicated indices
+fn to_lc<Fr: PrimeField>(lc: &[(Num<Fr>, Index)]) -> VecLC<Fr> {
+    let mut res = VecLC::new();
+    for &(k, i) in lc.iter() {
+        res.add_assign(&VecLC::from_parts(k, i));
+    }
+    res
+}
+
+fn as_const<Fr: PrimeField>(lc: &VecLC<Fr>) -> Option<Num<Fr>> {
+    match lc.0[..] {
+        [] => Some(Num::ZERO),
+        [(k, Index::Input(0))] => Some(k),
+        _ => None,
+    }
+}
+
+// k * lc
+fn scaled<Fr: PrimeField>(lc: &VecLC<Fr>, k: Num<Fr>) -> VecLC<Fr> {
+    let mut res = lc.clone();
+    res.mul_assign(&k);
+    res
+}
+
+struct Optimizer<Fr: PrimeField> {
+    // linear definitions of the eliminated aux variables
+    defs: Vec<Option<VecLC<Fr>>>,
+    // elimination order
+    order: Vec<u32>,
+    // number of lcs, where the aux variable is used
+    uses: Vec<usize>,
+}
+
+impl<Fr: PrimeField> Optimizer<Fr> {
+    // substitute all eliminated variables
+    fn expand(&self, lc: &VecLC<Fr>) -> VecLC<Fr> {
+        let mut res = lc.clone();
+        // each substitution brings only variables eliminated later, so the loop is finite
+        while let Some((k, i)) = res.0.iter().find_map(|&(k, i)| match i {
+            Index::Aux(i) if self.defs[i as usize].is_some() => Some((k, i)),
+            _ => None,
+        }) {
+            res.sub_assign(&VecLC::from_parts(k, Index::Aux(i)));
+            res.add_assign(&scaled(self.defs[i as usize].as_ref().unwrap(), k));
+        }
+        res
+    }
+
+    // try to eliminate one aux variable from the linear constraint lc == 0, return false if the gate should be kept
+    fn eliminate(&mut self, lc: VecLC<Fr>) -> bool {
+        let lc = self.expand(&lc);
+        if lc.is_empty() {
+            return true;
+        }
+
+        let len = lc.0.len();
+        let candidate = lc
+            .0
+            .iter()
+            .filter_map(|&(k, i)| match i {
+                Index::Aux(i) if !k.is_zero() => Some((k, i)),
+                _ => None,
+            })
+            .min_by_key(|&(_, i)| self.uses[i as usize]);
+
+        let (k, x) = match candidate {
+            Some(c) => c,
+            None => return false,
+        };
+
+        // substitution replaces one term with len-1 terms in each other lc, where x is used,
+        // so it is done only when it does not increase the total number of terms much
+        let other_uses = self.uses[x as usize].saturating_sub(1);
+        if len > 2 && other_uses * (len - 2) > len {
+            return false;
+        }
+
+        let mut def = lc;
+        def.sub_assign(&VecLC::from_parts(k, Index::Aux(x)));
+        def.mul_assign(&-k.checked_inv().unwrap());
+
+        for &(_, i) in def.0.iter() {
+            if let Index::Aux(i) = i {
+                self.uses[i as usize] += other_uses;
+            }
+        }
+        self.defs[x as usize] = Some(def);
+        self.order.push(x);
+        true
+    }
+}
+
+fn gate_key<Fr: PrimeField>(a: &VecLC<Fr>, b: &VecLC<Fr>, c: &VecLC<Fr>) -> Vec<Vec<(NumRepr<Fr::Inner>, Index)>> {
+    [a, b, c]
+        .iter()
+        .map(|lc| lc.0.iter().map(|(k, i)| (k.to_mont_uint(), *i)).collect())
+        .collect()
+}
+
+// Return optimized constraint system and the map from the new aux indices to the original ones
+pub fn optimize<Fr: PrimeField, L: AbstractLC<Fr>>(cs: &BuildCS<Fr, L>) -> (BuildCS<Fr, L>, Vec<u32>) {
+    let mut uses = vec![0; cs.num_aux];
+    for g in cs.gates.iter() {
+        for lc in [&g.0, &g.1, &g.2].iter() {
+            let mut vars = lc
+                .iter()
+                .filter_map(|(_, i)| match i {
+                    Index::Aux(i) => Some(*i),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            vars.sort_unstable();
+            vars.dedup();
+            for i in vars {
+                uses[i as usize] += 1;
+            }
+        }
+    }
+
+    let mut opt = Optimizer {
+        defs: vec![None; cs.num_aux],
+        order: vec![],
+        uses,
+    };
+
+    let mut kept = vec![];
+    for (n, g) in cs.gates.iter().enumerate() {
+        let (a, b, c) = (to_lc(&g.0), to_lc(&g.1), to_lc(&g.2));
+        let linear = match (as_const(&a), as_const(&b)) {
+            (Some(k), _) => Some(scaled(&b, k)),
+            (None, Some(k)) => Some(scaled(&a, k)),
+            _ => None,
+        };
+        let removed = match linear {
+            Some(mut lc) => {
+                lc.sub_assign(&c);
+                opt.eliminate(lc)
+            }
+            None => false,
+        };
+        if !removed {
+            kept.push((n, a, b, c));
+        }
+    }
+
+    // resolve definitions from the last eliminated variable to the first one, so each expand is one level deep
+    for &x in opt.order.clone().iter().rev() {
+        let def = opt.expand(opt.defs[x as usize].as_ref().unwrap());
+        opt.defs[x as usize] = Some(def);
+    }
+
+    let mut aux_map = vec![];
+    let mut new_index = vec![None; cs.num_aux];
+    for i in 0..cs.num_aux {
+        if opt.defs[i].is_none() {
+            new_index[i] = Some(aux_map.len() as u32);
+            aux_map.push(i as u32);
+        }
+    }
+
+    let renumber = |lc: VecLC<Fr>| -> VecLC<Fr> {
+        VecLC(
+            lc.0.into_iter()
+                .map(|(k, i)| match i {
+                    Index::Aux(i) => (k, Index::Aux(new_index[i as usize].unwrap())),
+                    _ => (k, i),
+                })
+                .collect(),
+        )
+    };
+
+    let mut res = BuildCS::<Fr, L>::new_with_lc();
+    res.num_input = cs.num_input;
+    res.num_aux = aux_map.len();
+    res.const_tracker = cs.const_tracker.clone();
+    res.scopes = cs.scopes.clone();
+    res.aux_scopes = aux_map.iter().map(|&i| cs.aux_scopes[i as usize]).collect();
+    res.unchecked_bits = cs
+        .unchecked_bits
+        .iter()
+        .filter_map(|&i| new_index[i as usize])
+        .collect();
+
+    let mut known = HashSet::new();
+    for (n, a, b, c) in kept {
+        let a = renumber(opt.expand(&a));
+        let b = renumber(opt.expand(&b));
+        let c = renumber(opt.expand(&c));
+
+        // gates became constant after the substitution
+        if let (Some(ka), Some(kb), Some(kc)) = (as_const(&a), as_const(&b), as_const(&c)) {
+            if ka * kb == kc {
+                continue;
+            }
+        }
+
+        if known.contains(&gate_key(&b, &a, &c)) || !known.insert(gate_key(&a, &b, &c)) {
+            continue;
+        }
+
+        res.gates.push(Gate(a.0, b.0, c.0));
+        res.gate_scopes.push(cs.gate_scopes[n]);
+    }
+
+    (res, aux_map)
+}
+
+// Witness values of the aux variables of the optimized constraint system
+pub fn map_aux<Fr: PrimeField>(values_aux: &[Num<Fr>], aux_map: &[u32]) -> Vec<Num<Fr>> {
+    aux_map.iter().map(|&i| values_aux[i as usize]).collect()
+}
@@ -0,0 +1,140 @@
+// Per scope constraint counters. Counters of each scope include counters of nested scopes.
+#[derive(Clone, Debug, Default)]
+pub struct ScopeProfile {
+    pub name: String,
+    pub num_calls: usize,
+    pub num_gates: usize,
+    pub num_aux: usize,
+    // total number of nonzero coefficients in a, b and c of the gates
+    pub lc_density: usize,
+    pub children: Vec<ScopeProfile>,
+}
+
+impl ScopeProfile {
+    // find nested scope by path like "poseidon_merkle_proof_root/poseidon"
+    pub fn get(&self, path: &str) -> Option<&ScopeProfile> {
+        path.split('/')
+            .try_fold(self, |p, name| p.children.iter().find(|c| c.name == name))
+    }
+
+    fn fmt_level(&self, f: &mut std::fmt::Formatter<'_>, level: usize) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:indent$}{} (calls={}): gates={}, aux={}, lc_density={}",
+            "",
+            if self.name.is_empty() { "<root>" } else { &self.name },
+            self.num_calls,
+            self.num_gates,
+            self.num_aux,
+            self.lc_density,
+            indent = 2 * level
+        )?;
+        for c in self.children.iter() {
+            c.fmt_level(f, level + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for ScopeProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_level(f, 0)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ProfileNode {
+    name: String,
+    children: Vec<usize>,
+    num_calls: usize,
+    num_gates: usize,
+    num_aux: usize,
+    lc_density: usize,
+}
+
+impl ProfileNode {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            children: vec![],
+            num_calls: 0,
+            num_gates: 0,
+            num_aux: 0,
+            lc_density: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Profiler {
+    nodes: Vec<ProfileNode>,
+    stack: Vec<usize>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        let mut root = ProfileNode::new("");
+        root.num_calls = 1;
+        Self {
+            nodes: vec![root],
+            stack: vec![0],
+        }
+    }
+
+    fn current(&mut self) -> &mut ProfileNode {
+        let id = *self.stack.last().unwrap();
+        &mut self.nodes[id]
+    }
+
+    pub fn push(&mut self, name: &str) {
+        let parent = *self.stack.last().unwrap();
+        let found = self.nodes[parent]
+            .children
+            .iter()
+            .cloned()
+            .find(|&c| self.nodes[c].name == name);
+        let id = match found {
+            Some(id) => id,
+            None => {
+                self.nodes.push(ProfileNode::new(name));
+                let id = self.nodes.len() - 1;
+                self.nodes[parent].children.push(id);
+                id
+            }
+        };
+        self.nodes[id].num_calls += 1;
+        self.stack.push(id);
+    }
+
+    pub fn pop(&mut self) {
+        assert!(self.stack.len() > 1, "scope stack is empty");
+        self.stack.pop();
+    }
+
+    pub fn add_gate(&mut self, lc_density: usize) {
+        let node = self.current();
+        node.num_gates += 1;
+        node.lc_density += lc_density;
+    }
+
+    pub fn add_aux(&mut self) {
+        self.current().num_aux += 1;
+    }
+
+    fn profile(&self, id: usize) -> ScopeProfile {
+        let node = &self.nodes[id];
+        let children: Vec<_> = node.children.iter().map(|&c| self.profile(c)).collect();
+        ScopeProfile {
+            name: node.name.clone(),
+            num_calls: node.num_calls,
+            num_gates: node.num_gates + children.iter().map(|c| c.num_gates).sum::<usize>(),
+            num_aux: node.num_aux + children.iter().map(|c| c.num_aux).sum::<usize>(),
+            lc_density: node.lc_density + children.iter().map(|c| c.lc_density).sum::<usize>(),
+            children,
+        }
+    }
+
+    pub fn report(&self) -> ScopeProfile {
+        self.profile(0)
+    }
+}
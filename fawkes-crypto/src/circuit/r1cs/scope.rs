@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use crate::circuit::cs::{CS, RCS};
+
+// Guard, which keeps the named gadget scope open until it is dropped
+pub struct Scope<C: CS>(RCS<C>);
+
+impl<C: CS> Scope<C> {
+    pub fn new(cs: &RCS<C>, name: &str) -> Self {
+        cs.borrow_mut().push_scope(name);
+        Scope(cs.clone())
+    }
+}
+
+impl<C: CS> Drop for Scope<C> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().pop_scope();
+    }
+}
+
+// Interned full paths of the nested scopes, the root scope has id 0 and empty path
+#[derive(Clone, Debug)]
+pub struct ScopeNames {
+    pub paths: Vec<String>,
+    pub stack: Vec<usize>,
+    // ids of the child scopes by name for each scope, so the path is formatted only once
+    pub children: Vec<HashMap<String, usize>>,
+}
+
+impl ScopeNames {
+    pub fn new() -> Self {
+        Self {
+            paths: vec![String::new()],
+            stack: vec![0],
+            children: vec![HashMap::new()],
+        }
+    }
+
+    pub fn current(&self) -> usize {
+        *self.stack.last().unwrap()
+    }
+
+    pub fn get(&self, id: usize) -> &str {
+        &self.paths[id]
+    }
+
+    pub fn push(&mut self, name: &str) {
+        let parent = self.current();
+        let id = match self.children[parent].get(name) {
+            Some(&id) => id,
+            None => {
+                let path = if parent == 0 {
+                    name.to_string()
+                } else {
+                    format!("{}/{}", self.paths[parent], name)
+                };
+                let id = self.paths.len();
+                self.paths.push(path);
+                self.children.push(HashMap::new());
+                self.children[parent].insert(name.to_string(), id);
+                id
+            }
+        };
+        self.stack.push(id);
+    }
+
+    pub fn pop(&mut self) {
+        assert!(self.stack.len() > 1, "scope stack is empty");
+        self.stack.pop();
+    }
+}
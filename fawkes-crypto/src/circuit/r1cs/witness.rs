@@ -0,0 +1,43 @@
+#[cfg(feature = "borsh_support")]
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    circuit::{circom, cs::WitnessCS},
+    ff_uint::{Num, PrimeField},
+};
+
+// Assignment of all variables, which is enough to create the proof without running the circuit
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "borsh_support", derive(BorshSerialize, BorshDeserialize))]
+pub struct Witness<Fr: PrimeField> {
+    pub values_input: Vec<Num<Fr>>,
+    pub values_aux: Vec<Num<Fr>>,
+}
+
+impl<Fr: PrimeField> Witness<Fr> {
+    pub fn from_cs(cs: &WitnessCS<Fr>) -> Self {
+        Self {
+            values_input: cs.values_input.clone(),
+            values_aux: cs.values_aux.clone(),
+        }
+    }
+
+    // public inputs without the constant one
+    pub fn inputs(&self) -> &[Num<Fr>] {
+        &self.values_input[1..]
+    }
+
+    pub fn write_wtns<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        circom::write_wtns(writer, self.values_input.iter().chain(self.values_aux.iter()))
+    }
+
+    // .wtns file does not separate inputs from aux variables, so the number of inputs (including the constant one)
+    // should be provided
+    pub fn read_wtns<R: std::io::Read>(reader: &mut R, num_input: usize) -> std::io::Result<Self> {
+        let values = circom::read_wtns(reader)?;
+        if values.len() < num_input {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong number of witness values"));
+        }
+        circom::split_witness(&values, num_input, values.len() - num_input)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{
+    circuit::{cs::CS, mimc::c_pow, num::CNum, scope::Scope},
+    core::signal::Signal,
+    ff_uint::Num,
+    native::rescue::{pow_inv, RescueParams},
+};
+use itertools::Itertools;
+
+// The root is allocated and checked by raising it to alpha, so it costs as much as the direct S-box
+fn c_pow_inv<C: CS>(a: &CNum<C>, params: &RescueParams<C::Fr>) -> CNum<C> {
+    let value = a.get_value().map(|a| pow_inv(a, params));
+    let res: CNum<C> = a.derive_alloc(value.as_ref());
+    CS::enforce(&c_pow(&res, params.alpha - 1), &res, a);
+    res
+}
+
+fn mix<C: CS>(state: &mut [CNum<C>], params: &RescueParams<C::Fr>) {
+    let cs = state[0].get_cs();
+    let mut new_state = vec![CNum::from_const(cs, &Num::ZERO); state.len()];
+    for (i, row) in params.m.iter().enumerate() {
+        for (m, s) in row.iter().zip(state.iter()) {
+            new_state[i] += *m * s;
+        }
+    }
+    state.clone_from_slice(&new_state);
+}
+
+fn ark<C: CS>(state: &mut [CNum<C>], c: &[Num<C::Fr>]) {
+    state.iter_mut().zip(c.iter()).for_each(|(e, c)| *e += c);
+}
+
+fn perm<C: CS>(state: &mut [CNum<C>], params: &RescueParams<C::Fr>) {
+    assert!(state.len() == params.t);
+    for i in 0..params.rounds {
+        state.iter_mut().for_each(|s| *s = c_pow(s, params.alpha));
+        mix(state, params);
+        ark(state, &params.c[2 * i]);
+
+        state.iter_mut().for_each(|s| *s = c_pow_inv(s, params));
+        mix(state, params);
+        ark(state, &params.c[2 * i + 1]);
+    }
+}
+
+pub fn c_rescue_perm<C: CS>(state: &[CNum<C>], params: &RescueParams<C::Fr>) -> Vec<CNum<C>> {
+    let _scope = Scope::new(state[0].get_cs(), "rescue_perm");
+    let mut state = state.to_vec();
+    perm(&mut state, params);
+    state
+}
+
+pub fn c_rescue_sponge<C: CS>(inputs: &[CNum<C>], params: &RescueParams<C::Fr>) -> CNum<C> {
+    assert!(!inputs.is_empty(), "should be at least one input");
+    let cs = inputs[0].get_cs();
+    let _scope = Scope::new(cs, "rescue_sponge");
+    let mut state = vec![CNum::from_const(cs, &Num::ZERO); params.t];
+    let size = CNum::from_const(cs, &Num::from(inputs.len() as u64));
+    core::iter::once(&size).chain(inputs.iter()).chunks(params.t - 1).into_iter().for_each(|c| {
+        state.iter_mut().zip(c).for_each(|(l, r)| *l += r);
+        perm(&mut state, params);
+    });
+    state[0].clone()
+}
@@ -0,0 +1,140 @@
+use crate::{
+    circuit::{
+        bitify::{c_bits_be_to_bytes, c_bytes_to_bits_be, c_from_bits_le},
+        bool::CBool,
+        cs::CS,
+        num::CNum,
+        scope::Scope,
+        word::{add, const_word, rotr, Word},
+    },
+    core::signal::Signal,
+    ff_uint::Num,
+    native::sha256::{sha256_padding, IV, ROUND_CONSTANTS},
+};
+
+// The hash bits are big endian in each 32-bit word and the words are little endian, see word::Word,
+// so the bits of each word are reversed
+fn words_from_bits<C: CS>(bits: &[CBool<C>]) -> Vec<Word<C>> {
+    bits.chunks(32).map(|c| c.iter().rev().cloned().collect()).collect()
+}
+
+fn words_to_bits<C: CS>(words: &[Word<C>]) -> Vec<CBool<C>> {
+    words.iter().flat_map(|w| w.iter().rev().cloned()).collect()
+}
+
+fn shr<C: CS>(w: &Word<C>, n: usize) -> Word<C> {
+    (0..32)
+        .map(|i| if i + n < 32 { w[i + n].clone() } else { w[0].derive_const(&false) })
+        .collect()
+}
+
+fn xor<C: CS>(a: &CNum<C>, b: &CNum<C>) -> CNum<C> {
+    let ab = a * b;
+    a + b - ab * Num::from(2)
+}
+
+// 2 constraints per bit, the constant bits of shr are folded.
+// The result is boolean, so it is packed without a check.
+fn xor3<C: CS>(a: &Word<C>, b: &Word<C>, c: &Word<C>) -> CNum<C> {
+    let bits = (0..32)
+        .map(|i| xor(&xor(a[i].as_num(), b[i].as_num()), c[i].as_num()))
+        .collect::<Vec<_>>();
+    pack(&bits)
+}
+
+// g + e * (f - g), 1 constraint per bit. The result is boolean, so it is packed without a check.
+fn ch<C: CS>(e: &Word<C>, f: &Word<C>, g: &Word<C>) -> CNum<C> {
+    let bits = (0..32)
+        .map(|i| g[i].as_num() + e[i].as_num() * (f[i].as_num() - g[i].as_num()))
+        .collect::<Vec<_>>();
+    pack(&bits)
+}
+
+// a * b + c * (a xor b), 2 constraints per bit. The result is boolean, so it is packed without a check.
+fn maj<C: CS>(a: &Word<C>, b: &Word<C>, c: &Word<C>) -> CNum<C> {
+    let bits = (0..32)
+        .map(|i| {
+            let ab = a[i].as_num() * b[i].as_num();
+            let a_xor_b = a[i].as_num() + b[i].as_num() - &ab * Num::from(2);
+            ab + c[i].as_num() * a_xor_b
+        })
+        .collect::<Vec<_>>();
+    pack(&bits)
+}
+
+fn pack<C: CS>(bits: &[CNum<C>]) -> CNum<C> {
+    let mut acc = bits[0].clone();
+    let mut k = Num::ONE;
+    for b in bits[1..].iter() {
+        k = k.double();
+        acc += b * k;
+    }
+    acc
+}
+
+fn compress<C: CS>(state: &[Word<C>], block: &[CBool<C>]) -> Vec<Word<C>> {
+    let mut w = words_from_bits(block);
+    for t in 16..64 {
+        let s0 = xor3(&rotr(&w[t - 15], 7), &rotr(&w[t - 15], 18), &shr(&w[t - 15], 3));
+        let s1 = xor3(&rotr(&w[t - 2], 17), &rotr(&w[t - 2], 19), &shr(&w[t - 2], 10));
+        let next = add(&[s1, c_from_bits_le(&w[t - 7]), s0, c_from_bits_le(&w[t - 16])], 4);
+        w.push(next);
+    }
+
+    let mut s = state.to_vec();
+    for t in 0..64 {
+        let (a, b, c, e, f, g) = (&s[0], &s[1], &s[2], &s[4], &s[5], &s[6]);
+        let s1 = xor3(&rotr(e, 6), &rotr(e, 11), &rotr(e, 25));
+        let t1 = c_from_bits_le(&s[7])
+            + s1
+            + ch(e, f, g)
+            + Num::from(ROUND_CONSTANTS[t] as u64)
+            + c_from_bits_le(&w[t]);
+        let s0 = xor3(&rotr(a, 2), &rotr(a, 13), &rotr(a, 22));
+        let t2 = s0 + maj(a, b, c);
+
+        // t1 is the sum of 5 words and t2 is the sum of 2 words
+        let new_e = add(&[c_from_bits_le(&s[3]), t1.clone()], 6);
+        let new_a = add(&[t1, t2], 7);
+        s.rotate_right(1);
+        s[0] = new_a;
+        s[4] = new_e;
+    }
+
+    state
+        .iter()
+        .zip(s.iter())
+        .map(|(a, b)| add(&[c_from_bits_le(a), c_from_bits_le(b)], 2))
+        .collect()
+}
+
+// Compress the 512-bit block into the 256-bit state, the bits are in the big endian order
+pub fn c_sha256_compress<C: CS>(state: &[CBool<C>], block: &[CBool<C>]) -> Vec<CBool<C>> {
+    assert!(state.len() == 256, "state should be 256 bits");
+    assert!(block.len() == 512, "block should be 512 bits");
+    let _scope = Scope::new(state[0].get_cs(), "sha256_compress");
+    words_to_bits(&compress(&words_from_bits(state), block))
+}
+
+// SHA-256 of the message bits, the most significant bit of each byte goes first.
+// The padding depends only on the length, so it is constant.
+pub fn c_sha256<C: CS>(bits: &[CBool<C>]) -> Vec<CBool<C>> {
+    assert!(!bits.is_empty(), "should be at least one input bit");
+    let _scope = Scope::new(bits[0].get_cs(), "sha256");
+    let padding = sha256_padding(bits.len())
+        .iter()
+        .map(|b| bits[0].derive_const(b))
+        .collect::<Vec<_>>();
+    let message = [bits, &padding].concat();
+
+    let mut state = IV.iter().map(|v| const_word(&bits[0], *v)).collect::<Vec<_>>();
+    for block in message.chunks(512) {
+        state = compress(&state, block);
+    }
+    words_to_bits(&state)
+}
+
+// SHA-256 of the message bytes, like native sha256. The bytes are range checked and the digest is returned as 32 bytes.
+pub fn c_sha256_bytes<C: CS>(bytes: &[CNum<C>]) -> Vec<CNum<C>> {
+    c_bits_be_to_bytes(&c_sha256(&c_bytes_to_bits_be(bytes)))
+}
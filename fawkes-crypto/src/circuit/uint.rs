@@ -0,0 +1,280 @@
+#[cfg(not(feature = "multithread"))]
+use std::cell::OnceCell;
+// the cached bits should not make the signal !Sync
+#[cfg(feature = "multithread")]
+use std::sync::OnceLock as OnceCell;
+use std::convert::{TryFrom, TryInto};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Shl, Shr, Sub};
+
+use crate::{
+    circuit::{
+        bitify::{c_from_bits_le, c_into_bits_le, c_lt},
+        bool::CBool,
+        cs::{CS, RCS},
+        divrem::c_div_rem,
+        num::CNum,
+    },
+    core::signal::{read_input, Signal},
+    ff_uint::{Num, NumRepr, PrimeFieldParams},
+};
+
+pub type UintRepr<C> = NumRepr<<<C as CS>::Fr as PrimeFieldParams>::Inner>;
+
+pub type CU8<C> = CUint<C, 8>;
+pub type CU16<C> = CUint<C, 16>;
+pub type CU32<C> = CUint<C, 32>;
+pub type CU64<C> = CUint<C, 64>;
+
+// Unsigned integer with BITS bits. The range is checked at alloc and after each arithmetic operation,
+// so the circuit is not satisfiable if the value overflows.
+// The product and the division need 2 * BITS + 1 bits below the field modulus, so for BN254 and BLS12-381
+// the widest uint with all operations is 126 bits. Wider uints panic on mul and div_rem.
+// The bit decomposition is computed only when it is needed and then cached.
+#[derive(Clone, Debug)]
+pub struct CUint<C: CS, const BITS: usize> {
+    num: CNum<C>,
+    bits: OnceCell<Vec<CBool<C>>>,
+}
+
+impl<C: CS, const BITS: usize> CUint<C, BITS> {
+    fn check_bits(extra: usize) {
+        assert!(BITS > 0, "uint should have at least one bit");
+        assert!(
+            BITS + extra < Num::<C::Fr>::MODULUS_BITS as usize,
+            "uint is too large for the field"
+        );
+    }
+
+    fn in_range(value: UintRepr<C>) -> bool {
+        (value >> BITS as u32).is_zero()
+    }
+
+    // The caller should ensure, that the value is in range
+    pub fn from_num_unchecked(num: CNum<C>) -> Self {
+        Self::check_bits(1);
+        Self { num, bits: OnceCell::new() }
+    }
+
+    // Range check the num
+    pub fn from_num(num: &CNum<C>) -> Self {
+        Self::check_bits(1);
+        let bits = c_into_bits_le(num, BITS);
+        Self::from_bits_checked(num.clone(), bits)
+    }
+
+    fn from_bits_checked(num: CNum<C>, bits: Vec<CBool<C>>) -> Self {
+        let cell = OnceCell::new();
+        let _ = cell.set(bits);
+        Self { num, bits: cell }
+    }
+
+    pub fn from_bits(bits: &[CBool<C>]) -> Self {
+        assert!(bits.len() == BITS, "wrong number of bits");
+        Self::check_bits(1);
+        Self::from_bits_checked(c_from_bits_le(bits), bits.to_vec())
+    }
+
+    pub fn as_num(&self) -> &CNum<C> {
+        &self.num
+    }
+
+    pub fn to_num(&self) -> CNum<C> {
+        self.num.clone()
+    }
+
+    // Little endian bits, computed at the first call
+    pub fn bits(&self) -> &[CBool<C>] {
+        self.bits.get_or_init(|| c_into_bits_le(&self.num, BITS))
+    }
+
+    // Return true if self < other
+    pub fn lt(&self, other: &Self) -> CBool<C> {
+        Self::check_bits(2);
+        c_lt(&self.num, &other.num, BITS)
+    }
+
+    pub fn le(&self, other: &Self) -> CBool<C> {
+        !other.lt(self)
+    }
+
+    pub fn gt(&self, other: &Self) -> CBool<C> {
+        other.lt(self)
+    }
+
+    pub fn ge(&self, other: &Self) -> CBool<C> {
+        !self.lt(other)
+    }
+
+    // Return (self / other, self % other), the circuit is not satisfiable if other is zero
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        Self::check_bits(BITS + 1);
+        let (q, r) = c_div_rem(&self.num, &other.num, BITS);
+        (Self::from_num_unchecked(q), Self::from_num_unchecked(r))
+    }
+
+    // Constant from a native integer like u64 or u128
+    pub fn from_const_native<T: Into<UintRepr<C>>>(cs: &RCS<C>, value: T) -> Self {
+        Self::from_const(cs, &value.into())
+    }
+
+    pub fn alloc_native<T: Into<UintRepr<C>>>(cs: &RCS<C>, value: Option<T>) -> Self {
+        Self::alloc(cs, value.map(Into::into).as_ref())
+    }
+
+    // Value as a native integer like u64 or u128. Return None if the value is unknown or does not fit
+    pub fn get_value_native<T: TryFrom<UintRepr<C>>>(&self) -> Option<T> {
+        self.get_value()?.try_into().ok()
+    }
+
+    pub fn as_const_native<T: TryFrom<UintRepr<C>>>(&self) -> Option<T> {
+        self.as_const()?.try_into().ok()
+    }
+
+    fn bitwise(&self, other: &Self, f: impl Fn(&CBool<C>, &CBool<C>) -> CBool<C>) -> Self {
+        let bits = self
+            .bits()
+            .iter()
+            .zip(other.bits().iter())
+            .map(|(a, b)| f(a, b))
+            .collect::<Vec<_>>();
+        Self::from_bits(&bits)
+    }
+}
+
+impl<C: CS, const BITS: usize> Signal<C> for CUint<C, BITS> {
+    type Value = UintRepr<C>;
+
+    fn as_const(&self) -> Option<Self::Value> {
+        self.num.as_const().map(|v| v.to_uint())
+    }
+
+    fn get_value(&self) -> Option<Self::Value> {
+        self.num.get_value().map(|v| v.to_uint())
+    }
+
+    fn from_const(cs: &RCS<C>, value: &Self::Value) -> Self {
+        assert!(Self::in_range(*value), "value is out of range");
+        Self::from_num_unchecked(CNum::from_const(cs, &Num::from_uint(*value).unwrap()))
+    }
+
+    fn get_cs(&self) -> &RCS<C> {
+        self.num.get_cs()
+    }
+
+    fn alloc(cs: &RCS<C>, value: Option<&Self::Value>) -> Self {
+        let value = value.map(|v| Num::from_uint(*v).expect("value is out of the field"));
+        Self::from_num(&CNum::alloc(cs, value.as_ref()))
+    }
+
+    fn switch(&self, bit: &CBool<C>, if_else: &Self) -> Self {
+        Self::from_num_unchecked(self.num.switch(bit, &if_else.num))
+    }
+
+    fn assert_const(&self, value: &Self::Value) {
+        self.num.assert_const(&Num::from_uint(*value).expect("value is out of the field"))
+    }
+
+    fn assert_eq(&self, other: &Self) {
+        self.num.assert_eq(&other.num)
+    }
+
+    fn is_eq(&self, other: &Self) -> CBool<C> {
+        self.num.is_eq(&other.num)
+    }
+
+    fn inputize(&self) {
+        self.num.inputize()
+    }
+
+    fn write_inputs(value: &Self::Value, inputs: &mut Vec<Num<C::Fr>>) {
+        inputs.push(Num::from_uint(*value).expect("value is out of the field"));
+    }
+
+    fn read_inputs(inputs: &mut &[Num<C::Fr>]) -> Option<Self::Value> {
+        let value = read_input(inputs)?.to_uint();
+        if Self::in_range(value) {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+impl<C: CS, const BITS: usize> Add<&CUint<C, BITS>> for &CUint<C, BITS> {
+    type Output = CUint<C, BITS>;
+
+    // the sum is range checked
+    fn add(self, other: &CUint<C, BITS>) -> Self::Output {
+        CUint::from_num(&(&self.num + &other.num))
+    }
+}
+
+impl<C: CS, const BITS: usize> Sub<&CUint<C, BITS>> for &CUint<C, BITS> {
+    type Output = CUint<C, BITS>;
+
+    // underflow wraps to a large field element, so it is caught by the range check
+    fn sub(self, other: &CUint<C, BITS>) -> Self::Output {
+        CUint::from_num(&(&self.num - &other.num))
+    }
+}
+
+impl<C: CS, const BITS: usize> Mul<&CUint<C, BITS>> for &CUint<C, BITS> {
+    type Output = CUint<C, BITS>;
+
+    fn mul(self, other: &CUint<C, BITS>) -> Self::Output {
+        // the product should not wrap the field before the range check
+        CUint::<C, BITS>::check_bits(BITS);
+        CUint::from_num(&(&self.num * &other.num))
+    }
+}
+
+impl<C: CS, const BITS: usize> BitAnd<&CUint<C, BITS>> for &CUint<C, BITS> {
+    type Output = CUint<C, BITS>;
+
+    fn bitand(self, other: &CUint<C, BITS>) -> Self::Output {
+        self.bitwise(other, |a, b| a.clone() & b)
+    }
+}
+
+impl<C: CS, const BITS: usize> BitOr<&CUint<C, BITS>> for &CUint<C, BITS> {
+    type Output = CUint<C, BITS>;
+
+    fn bitor(self, other: &CUint<C, BITS>) -> Self::Output {
+        self.bitwise(other, |a, b| a.clone() | b)
+    }
+}
+
+impl<C: CS, const BITS: usize> BitXor<&CUint<C, BITS>> for &CUint<C, BITS> {
+    type Output = CUint<C, BITS>;
+
+    fn bitxor(self, other: &CUint<C, BITS>) -> Self::Output {
+        self.bitwise(other, |a, b| a.clone() ^ b)
+    }
+}
+
+// Shifts by a constant, the bits shifted out are dropped
+impl<C: CS, const BITS: usize> Shl<usize> for &CUint<C, BITS> {
+    type Output = CUint<C, BITS>;
+
+    fn shl(self, n: usize) -> Self::Output {
+        let bits = self.bits();
+        let zero: CBool<C> = bits[0].derive_const(&false);
+        let bits = (0..BITS)
+            .map(|i| if i < n { zero.clone() } else { bits[i - n].clone() })
+            .collect::<Vec<_>>();
+        CUint::from_bits(&bits)
+    }
+}
+
+impl<C: CS, const BITS: usize> Shr<usize> for &CUint<C, BITS> {
+    type Output = CUint<C, BITS>;
+
+    fn shr(self, n: usize) -> Self::Output {
+        let bits = self.bits();
+        let zero: CBool<C> = bits[0].derive_const(&false);
+        let bits = (0..BITS)
+            .map(|i| if i + n < BITS { bits[i + n].clone() } else { zero.clone() })
+            .collect::<Vec<_>>();
+        CUint::from_bits(&bits)
+    }
+}
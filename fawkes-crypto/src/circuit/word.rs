@@ -0,0 +1,65 @@
+use crate::{
+    circuit::{
+        bitify::{c_from_bits_le, c_into_bits_le},
+        bool::CBool,
+        cs::CS,
+        num::CNum,
+    },
+    core::signal::Signal,
+    ff_uint::Num,
+};
+
+// Helpers for the hashes over 32-bit words, like SHA-256 and Blake2s
+
+// 32-bit word, little endian bits
+pub type Word<C> = Vec<CBool<C>>;
+
+pub fn const_word<C: CS>(s: &CBool<C>, value: u32) -> Word<C> {
+    (0..32).map(|i| s.derive_const(&((value >> i) & 1 == 1))).collect()
+}
+
+pub fn rotr<C: CS>(w: &Word<C>, n: usize) -> Word<C> {
+    (0..32).map(|i| w[(i + n) % 32].clone()).collect()
+}
+
+// 1 constraint, free if one of the bits is constant.
+// The result is allocated, otherwise the linear combinations grow with each round.
+pub fn xor_bit<C: CS>(a: &CNum<C>, b: &CNum<C>) -> CBool<C> {
+    if a.as_const().is_some() || b.as_const().is_some() {
+        let ab = a * b;
+        return (a + b - ab * Num::from(2)).to_bool_unchecked();
+    }
+    let value = a.get_value().zip(b.get_value()).map(|(a, b)| a + b - a * b * Num::from(2));
+    let res: CNum<C> = a.derive_alloc(value.as_ref());
+    CS::enforce(&(a * Num::from(2)), b, &(a + b - &res));
+    res.to_bool_unchecked()
+}
+
+pub fn xor<C: CS>(a: &Word<C>, b: &Word<C>) -> Word<C> {
+    a.iter().zip(b.iter()).map(|(a, b)| xor_bit(a.as_num(), b.as_num())).collect()
+}
+
+// Sum modulo 2^32 of the terms, which are the sums of n_words words in total.
+// The bits of the sum are computed once for all the terms. The sum is allocated, otherwise the lowest bit
+// keeps the whole linear combination of the sum and it grows with each round.
+pub fn add<C: CS>(terms: &[CNum<C>], n_words: usize) -> Word<C> {
+    let mut sum = terms[0].clone();
+    for t in terms[1..].iter() {
+        sum += t;
+    }
+    if sum.as_const().is_none() {
+        let value: CNum<C> = sum.derive_alloc(sum.get_value().as_ref());
+        value.assert_eq(&sum);
+        sum = value;
+    }
+    let carry_bits = (usize::BITS - (n_words - 1).leading_zeros()) as usize;
+    let mut bits = c_into_bits_le(&sum, 32 + carry_bits);
+    bits.truncate(32);
+    bits
+}
+
+// Sum modulo 2^32 of the words
+pub fn add_words<C: CS>(words: &[&Word<C>]) -> Word<C> {
+    let terms = words.iter().map(|w| c_from_bits_le(w)).collect::<Vec<_>>();
+    add(&terms, words.len())
+}
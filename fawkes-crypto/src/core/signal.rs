@@ -1,10 +1,11 @@
 use crate::{
     circuit::{bool::CBool, cs::{CS, RCS}},
     core::sizedvec::SizedVec,
+    ff_uint::Num,
 };
 use impl_trait_for_tuples::impl_for_tuples;
 
-pub use fawkes_crypto_derive::Signal;
+pub use fawkes_crypto_derive::{enum_signal, Signal};
 
 pub trait Signal<C: CS>: Sized + Clone {
     type Value: Clone + Sized;
@@ -48,6 +49,53 @@ pub trait Signal<C: CS>: Sized + Clone {
     fn derive_alloc<T: Signal<C>>(&self, value: Option<&T::Value>) -> T {
         T::alloc(self.get_cs(), value)
     }
+
+    // Append the public inputs of the value, in the same order as inputize makes them.
+    // write_inputs and read_inputs are required since 5.0, #[derive(Signal)] implements them.
+    fn write_inputs(value: &Self::Value, inputs: &mut Vec<Num<C::Fr>>);
+
+    // Read the value from the public inputs and advance the slice
+    fn read_inputs(inputs: &mut &[Num<C::Fr>]) -> Option<Self::Value>;
+
+    // Public inputs of the value, as they are passed to verify
+    fn value_to_inputs(value: &Self::Value) -> Vec<Num<C::Fr>> {
+        let mut inputs = vec![];
+        Self::write_inputs(value, &mut inputs);
+        inputs
+    }
+
+    // Value from the public inputs, as they are returned by prove. Return None if the inputs do not match the signal
+    fn value_from_inputs(inputs: &[Num<C::Fr>]) -> Option<Self::Value> {
+        let mut inputs = inputs;
+        let value = Self::read_inputs(&mut inputs)?;
+        if inputs.is_empty() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+// Read one public input
+pub fn read_input<Fr: crate::ff_uint::PrimeField>(inputs: &mut &[Num<Fr>]) -> Option<Num<Fr>> {
+    let (first, rest) = inputs.split_first()?;
+    *inputs = rest;
+    Some(*first)
+}
+
+// Signal, which public part is marked with #[public] attributes on the fields of the derived struct
+pub trait PublicSignal<C: CS>: Signal<C> {
+    // Values of the public fields, in the order of the public inputs
+    type PublicValue: Clone + Sized;
+
+    // Make the public fields public inputs, in the order of #[public] positions or of the declaration
+    fn inputize_public(&self);
+
+    // Public part of the value
+    fn public_value(value: &Self::Value) -> Self::PublicValue;
+
+    // Append the public inputs of the public part, in the same order as inputize_public makes them
+    fn write_public_inputs(value: &Self::PublicValue, inputs: &mut Vec<Num<C::Fr>>);
 }
 
 impl<C: CS, T: Signal<C>, const L: usize> Signal<C> for SizedVec<T, L> {
@@ -106,6 +154,16 @@ impl<C: CS, T: Signal<C>, const L: usize> Signal<C> for SizedVec<T, L> {
         }
         acc
     }
+
+    fn write_inputs(value: &Self::Value, inputs: &mut Vec<Num<C::Fr>>) {
+        value.iter().for_each(|v| T::write_inputs(v, inputs));
+    }
+
+    fn read_inputs(inputs: &mut &[Num<C::Fr>]) -> Option<Self::Value> {
+        // SizedVec could not be collected from a shorter iterator, so the values are collected to Vec first
+        let values = (0..L).map(|_| T::read_inputs(inputs)).collect::<Option<Vec<_>>>()?;
+        Some(values.into_iter().collect())
+    }
 }
 
 #[impl_for_tuples(1, 17)]
@@ -156,4 +214,12 @@ impl<C: CS> Signal<C> for Tuple {
         for_tuples!( #(acc &= self.Tuple.is_eq(&other.Tuple); )* );
         acc
     }
+
+    fn write_inputs(value: &Self::Value, inputs: &mut Vec<Num<C::Fr>>) {
+        for_tuples!( #(Tuple::write_inputs(&value.Tuple, inputs); )* );
+    }
+
+    fn read_inputs(inputs: &mut &[Num<C::Fr>]) -> Option<Self::Value> {
+        Some((for_tuples!( #( Tuple::read_inputs(inputs)?),* )))
+    }
 }
@@ -36,6 +36,12 @@ impl<T, const L: usize> SizedVec<T, L> {
     }
 }
 
+impl<T: Default, const L: usize> Default for SizedVec<T, L> {
+    fn default() -> Self {
+        (0..L).map(|_| T::default()).collect()
+    }
+}
+
 #[cfg(feature = "serde_support")]
 impl<T: Serialize, const L: usize> Serialize for SizedVec<T, L> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
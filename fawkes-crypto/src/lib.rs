@@ -23,3 +23,6 @@ pub extern crate ff_uint;
 pub extern crate rand;
 
 pub extern crate fawkes_crypto_derive;
+
+// the code generated by fawkes_crypto_derive refers to the crate by name, also inside the crate
+extern crate self as fawkes_crypto_zkbob;
@@ -213,3 +213,42 @@ macro_rules! swap_commutative {
         swap_commutative_ref_ref!(impl<$($imp_l, )*$($imp_i : $imp_p),+> $imp<$res2> for $res, $method);
     };
 }
+
+// Helpers for the code generated by fawkes_crypto_derive. They are expanded in the crate with the derive,
+// but depend on the features of fawkes-crypto.
+
+#[cfg(feature = "serde_support")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __derive_serde {
+    ($serde:tt, $item:item) => {
+        #[derive($crate::serde::Serialize, $crate::serde::Deserialize)]
+        #[serde(crate = $serde, bound(serialize = "", deserialize = ""))]
+        $item
+    };
+}
+
+#[cfg(not(feature = "serde_support"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __derive_serde {
+    ($serde:tt, $item:item) => {
+        $item
+    };
+}
+
+#[cfg(feature = "borsh_support")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __borsh_support {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "borsh_support"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __borsh_support {
+    ($($item:item)*) => {};
+}
@@ -0,0 +1,31 @@
+// Bits of the bytes, the most significant bit of each byte goes first
+pub fn bytes_to_bits_be(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1 == 1))
+        .collect()
+}
+
+// Inverse of bytes_to_bits_be, the number of bits should be a multiple of 8
+pub fn bits_be_to_bytes(bits: &[bool]) -> Vec<u8> {
+    assert_eq!(bits.len() % 8, 0, "number of bits should be a multiple of 8");
+    bits.chunks(8)
+        .map(|c| c.iter().fold(0u8, |acc, &b| (acc << 1) | b as u8))
+        .collect()
+}
+
+// Bits of the bytes, the least significant bit of each byte goes first
+pub fn bytes_to_bits_le(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|b| (0..8).map(move |i| (b >> i) & 1 == 1))
+        .collect()
+}
+
+// Inverse of bytes_to_bits_le, the number of bits should be a multiple of 8
+pub fn bits_le_to_bytes(bits: &[bool]) -> Vec<u8> {
+    assert_eq!(bits.len() % 8, 0, "number of bits should be a multiple of 8");
+    bits.chunks(8)
+        .map(|c| c.iter().rev().fold(0u8, |acc, &b| (acc << 1) | b as u8))
+        .collect()
+}
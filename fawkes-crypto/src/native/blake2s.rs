@@ -0,0 +1,38 @@
+use blake2_rfc::blake2s::Blake2s;
+
+// Blake2s starts from the same initial values as SHA-256
+use crate::native::sha256::IV;
+
+// Bytes compressed per block
+pub const BLOCK_BYTES: usize = 64;
+
+pub const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+// Initial state for the unkeyed 32-byte digest with the zero salt and the personalization
+pub fn initial_state(personalization: &[u8; 8]) -> [u32; 8] {
+    let mut h = IV;
+    h[0] ^= 0x01010020;
+    h[6] ^= u32::from_le_bytes([personalization[0], personalization[1], personalization[2], personalization[3]]);
+    h[7] ^= u32::from_le_bytes([personalization[4], personalization[5], personalization[6], personalization[7]]);
+    h
+}
+
+// Unkeyed 32-byte Blake2s digest with the zero salt, as used by eddsaposeidon
+pub fn blake2s(data: &[u8], personalization: &[u8; 8]) -> [u8; 32] {
+    let mut h = Blake2s::with_params(32, &[], &[], personalization);
+    h.update(data);
+    let mut res = [0; 32];
+    res.copy_from_slice(h.finalize().as_ref());
+    res
+}
@@ -0,0 +1,8 @@
+use crate::ff_uint::{Num, PrimeField};
+
+// Integer division of the field elements as unsigned integers, returns (a / b, a % b)
+pub fn div_rem<Fr: PrimeField>(a: Num<Fr>, b: Num<Fr>) -> (Num<Fr>, Num<Fr>) {
+    let (a, b) = (a.to_uint(), b.to_uint());
+    assert!(!b.is_zero(), "division by zero");
+    (Num::from_uint(a / b).unwrap(), Num::from_uint(a % b).unwrap())
+}
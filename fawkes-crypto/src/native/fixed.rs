@@ -0,0 +1,104 @@
+use core::cmp::Ordering;
+
+use crate::ff_uint::{Num, NumRepr, PrimeField};
+
+#[cfg(feature = "serde_support")]
+use crate::serde::{Deserialize, Serialize};
+
+// Rounding of the fractional bits dropped by the multiplication
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Truncate,
+    HalfUp,
+}
+
+// Unsigned fixed point number raw / 2^FRAC_BITS, where raw has INT_BITS + FRAC_BITS bits
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_support", serde(bound(serialize = "", deserialize = "")))]
+pub struct Fixed<Fr: PrimeField, const INT_BITS: usize, const FRAC_BITS: usize> {
+    raw: Num<Fr>,
+}
+
+// Return (q, r), where a * b + rounding offset = q * 2^frac_bits + r
+pub(crate) fn mul_raw<Fr: PrimeField>(
+    a: Num<Fr>,
+    b: Num<Fr>,
+    frac_bits: usize,
+    rounding: Rounding,
+) -> (Num<Fr>, Num<Fr>) {
+    let p = a.to_uint() * b.to_uint() + rounding_offset::<Fr>(frac_bits, rounding).to_uint();
+    let q = p >> frac_bits as u32;
+    let r = p - (q << frac_bits as u32);
+    (Num::from_uint(q).unwrap(), Num::from_uint(r).unwrap())
+}
+
+pub(crate) fn rounding_offset<Fr: PrimeField>(frac_bits: usize, rounding: Rounding) -> Num<Fr> {
+    match rounding {
+        Rounding::Truncate => Num::ZERO,
+        Rounding::HalfUp => Num::from_uint(NumRepr::ONE << (frac_bits as u32 - 1)).unwrap(),
+    }
+}
+
+impl<Fr: PrimeField, const INT_BITS: usize, const FRAC_BITS: usize> Fixed<Fr, INT_BITS, FRAC_BITS> {
+    pub const BITS: usize = INT_BITS + FRAC_BITS;
+
+    fn in_range(raw: NumRepr<Fr::Inner>) -> bool {
+        (raw >> Self::BITS as u32).is_zero()
+    }
+
+    // Return None if raw is out of range
+    pub fn from_raw(raw: Num<Fr>) -> Option<Self> {
+        if Self::in_range(raw.to_uint()) {
+            Some(Self { raw })
+        } else {
+            None
+        }
+    }
+
+    pub fn raw(&self) -> Num<Fr> {
+        self.raw
+    }
+
+    pub fn from_int(value: u64) -> Option<Self> {
+        Self::from_raw(Num::from_uint(NumRepr::from(value) << FRAC_BITS as u32)?)
+    }
+
+    // Return n / d, rounded to FRAC_BITS
+    pub fn from_ratio(n: u64, d: u64, rounding: Rounding) -> Option<Self> {
+        assert!(d != 0, "division by zero");
+        let (n, d) = (NumRepr::from(n) << FRAC_BITS as u32, NumRepr::<Fr::Inner>::from(d));
+        let raw = match rounding {
+            Rounding::Truncate => n / d,
+            Rounding::HalfUp => (n + n + d) / (d + d),
+        };
+        Self::from_raw(Num::from_uint(raw)?)
+    }
+
+    // Integer part of the number
+    pub fn to_int(&self) -> NumRepr<Fr::Inner> {
+        self.raw.to_uint() >> FRAC_BITS as u32
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Self::from_raw(self.raw + other.raw)
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if self.raw.to_uint() < other.raw.to_uint() {
+            None
+        } else {
+            Self::from_raw(self.raw - other.raw)
+        }
+    }
+
+    pub fn checked_mul(&self, other: &Self, rounding: Rounding) -> Option<Self> {
+        Self::from_raw(mul_raw(self.raw, other.raw, FRAC_BITS, rounding).0)
+    }
+}
+
+impl<Fr: PrimeField, const INT_BITS: usize, const FRAC_BITS: usize> PartialOrd for Fixed<Fr, INT_BITS, FRAC_BITS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.raw.to_uint().partial_cmp(&other.raw.to_uint())
+    }
+}
@@ -0,0 +1,72 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+// Bytes absorbed per permutation, 1600 - 2 * 256 bits
+pub const RATE: usize = 136;
+
+pub const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+// Rotation of the lane x + 5 * y
+pub const ROTATION_OFFSETS: [u32; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+// Position of the lane x + 5 * y after the pi step
+pub fn pi_index(i: usize) -> usize {
+    let (x, y) = (i % 5, i / 5);
+    y + 5 * ((2 * x + 3 * y) % 5)
+}
+
+// Bytes, which are appended to the message of len bytes: 0x01, zeros and 0x80 up to the multiple of RATE.
+// This is the original Keccak padding used by Ethereum, not the SHA-3 one.
+pub fn keccak256_padding(len: usize) -> Vec<u8> {
+    let mut res = vec![0u8; RATE - len % RATE];
+    res[0] |= 0x01;
+    *res.last_mut().unwrap() |= 0x80;
+    res
+}
+
+pub fn keccak_f1600(state: &mut [u64; 25]) {
+    for rc in ROUND_CONSTANTS.iter() {
+        let mut c = [0u64; 5];
+        for (i, s) in state.iter().enumerate() {
+            c[i % 5] ^= s;
+        }
+        for (i, s) in state.iter_mut().enumerate() {
+            *s ^= c[(i + 4) % 5] ^ c[(i + 1) % 5].rotate_left(1);
+        }
+
+        let mut b = [0u64; 25];
+        for (i, s) in state.iter().enumerate() {
+            b[pi_index(i)] = s.rotate_left(ROTATION_OFFSETS[i]);
+        }
+
+        for (i, s) in state.iter_mut().enumerate() {
+            let y = i - i % 5;
+            *s = b[i] ^ (!b[y + (i + 1) % 5] & b[y + (i + 2) % 5]);
+        }
+
+        state[0] ^= rc;
+    }
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let message = [data, &keccak256_padding(data.len())].concat();
+    let mut state = [0u64; 25];
+    for block in message.chunks(RATE) {
+        for (i, s) in state[..RATE / 8].iter_mut().enumerate() {
+            *s ^= LittleEndian::read_u64(&block[8 * i..]);
+        }
+        keccak_f1600(&mut state);
+    }
+
+    let mut res = [0u8; 32];
+    LittleEndian::write_u64_into(&state[..4], &mut res);
+    res
+}
@@ -0,0 +1,85 @@
+use ff_uint::{
+    seedbox::{SeedBox, SeedBoxGen, SeedboxChaCha20},
+    Num, NumRepr, PrimeField, Uint,
+};
+
+#[cfg(feature = "serde_support")]
+use crate::serde::{Deserialize, Serialize};
+
+// MiMC in the Feistel mode with x^alpha round function, as in circomlib MiMCFeistel and MiMCSponge.
+// The constants are generated via seedbox, so the constants of the contracts should be set explicitly
+// to be compatible with them.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde_support",
+    serde(bound(serialize = "", deserialize = ""))
+)]
+pub struct MimcParams<Fr: PrimeField> {
+    pub c: Vec<Num<Fr>>,
+    pub alpha: u64,
+}
+
+impl<Fr: PrimeField> MimcParams<Fr> {
+    pub fn new(alpha: u64, rounds: usize) -> Self {
+        Self::new_with_salt(alpha, rounds, "")
+    }
+
+    // The number of rounds should be chosen according to the field size, 220 for alpha = 5 and the 254-bit field
+    pub fn new_with_salt(alpha: u64, rounds: usize, salt: &str) -> Self {
+        assert!(alpha > 1, "alpha should be greater than 1");
+        // x^alpha is a permutation of the field only if gcd(alpha, r - 1) = 1
+        let rem = ((Num::<Fr>::MODULUS - NumRepr::ONE) % NumRepr::from(alpha)).0.low_u64();
+        assert!(gcd(alpha, rem) == 1, "alpha should be coprime with r - 1");
+        assert!(rounds > 0, "should be at least one round");
+        let mut seedbox = SeedboxChaCha20::new_with_salt(
+            format!("fawkes_mimc(alpha={},rounds={},salt={})", alpha, rounds, salt).as_bytes(),
+        );
+        let c = (0..rounds).map(|_| seedbox.gen()).collect();
+        MimcParams { c, alpha }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn pow<Fr: PrimeField>(a: Num<Fr>, exp: u64) -> Num<Fr> {
+    Num(a.0.pow(NumRepr::<Fr::Inner>::from(exp)))
+}
+
+// Feistel permutation of (xl, xr) with the key, the last round does not swap the halves
+pub fn mimc_feistel<Fr: PrimeField>(
+    mut xl: Num<Fr>,
+    mut xr: Num<Fr>,
+    k: Num<Fr>,
+    params: &MimcParams<Fr>,
+) -> (Num<Fr>, Num<Fr>) {
+    let rounds = params.c.len();
+    for (i, c) in params.c.iter().enumerate() {
+        let t = xr + pow(xl + k + c, params.alpha);
+        if i + 1 < rounds {
+            xr = xl;
+            xl = t;
+        } else {
+            xr = t;
+        }
+    }
+    (xl, xr)
+}
+
+// Sponge with the rate of 1 and the capacity of 1, each input is added to the left half
+pub fn mimc_sponge<Fr: PrimeField>(inputs: &[Num<Fr>], k: Num<Fr>, params: &MimcParams<Fr>) -> Num<Fr> {
+    assert!(!inputs.is_empty(), "should be at least one input");
+    let (mut xl, mut xr) = (Num::ZERO, Num::ZERO);
+    for input in inputs.iter() {
+        let res = mimc_feistel(xl + input, xr, k, params);
+        xl = res.0;
+        xr = res.1;
+    }
+    xl
+}
@@ -1,3 +1,12 @@
+pub mod bitify;
+pub mod blake2s;
+pub mod divrem;
 pub mod ecc;
 pub mod eddsaposeidon;
+pub mod fixed;
+pub mod keccak256;
+pub mod mimc;
+pub mod pedersen;
 pub mod poseidon;
+pub mod rescue;
+pub mod sha256;
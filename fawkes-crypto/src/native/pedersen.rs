@@ -0,0 +1,94 @@
+// The windowed Pedersen hash described here https://iden3.readthedocs.io/en/latest/iden3_repos/research/publications/zkproof-standards-workshop-2/pedersen-hash/pedersen.html
+// The message is split into the segments of 50 windows, each window of 4 bits b0..b3 encodes the signed value
+// (1 + b0 + 2 * b1 + 4 * b2) * (1 - 2 * b3), and the segment is the sum of the values multiplied by 2^(5 * k) * G,
+// where G is the generator of the segment. The generators are derived via seedbox from the personalization,
+// so the hash is not compatible with circomlib, which uses the same windows with other generators.
+
+use crate::{
+    ff_uint::{
+        seedbox::{SeedBox, SeedBoxGen, SeedboxChaCha20},
+        Num, PrimeField,
+    },
+    native::ecc::{EdwardsPoint, EdwardsPointEx, JubJubParams},
+};
+
+#[cfg(feature = "serde_support")]
+use crate::serde::{Deserialize, Serialize};
+
+pub const WINDOW_BITS: usize = 4;
+pub const SEGMENT_WINDOWS: usize = 50;
+pub const SEGMENT_BITS: usize = WINDOW_BITS * SEGMENT_WINDOWS;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde_support",
+    serde(bound(serialize = "", deserialize = ""))
+)]
+pub struct PedersenParams<Fr: PrimeField> {
+    pub generators: Vec<EdwardsPoint<Fr>>,
+}
+
+impl<Fr: PrimeField> PedersenParams<Fr> {
+    // The generators for the messages up to n_segments * SEGMENT_BITS bits
+    pub fn new<J: JubJubParams<Fr = Fr>>(personalization: &str, n_segments: usize, jubjub_params: &J) -> Self {
+        // the segment values are below 2^(5 * SEGMENT_WINDOWS - 2) by absolute value,
+        // so they should not wrap the half of the subgroup order
+        assert!(
+            5 * SEGMENT_WINDOWS <= Num::<J::Fs>::MODULUS_BITS as usize,
+            "segment is too large for the subgroup"
+        );
+        let mut seedbox = SeedboxChaCha20::new_with_salt(
+            format!("fawkes_pedersen(personalization={})", personalization).as_bytes(),
+        );
+        let generators = (0..n_segments)
+            .map(|_| EdwardsPoint::from_scalar(seedbox.gen(), jubjub_params))
+            .collect();
+        PedersenParams { generators }
+    }
+}
+
+// Multiples 2^(5 * k) * g of the windows of the segment
+pub(crate) fn window_bases<Fr: PrimeField>(g: &EdwardsPoint<Fr>) -> Vec<EdwardsPointEx<Fr>> {
+    let mut base = g.into_extended();
+    (0..SEGMENT_WINDOWS)
+        .map(|_| {
+            let res = base;
+            for _ in 0..5 {
+                base = base.double();
+            }
+            res
+        })
+        .collect()
+}
+
+fn window_value<Fs: PrimeField>(bits: &[bool]) -> Num<Fs> {
+    let b = |i: usize| bits.get(i).cloned().unwrap_or(false);
+    let v = Num::from(1 + b(0) as u64 + 2 * b(1) as u64 + 4 * b(2) as u64);
+    if b(3) {
+        -v
+    } else {
+        v
+    }
+}
+
+pub fn pedersen_hash<Fr: PrimeField, J: JubJubParams<Fr = Fr>>(
+    bits: &[bool],
+    params: &PedersenParams<Fr>,
+    jubjub_params: &J,
+) -> EdwardsPoint<Fr> {
+    assert!(!bits.is_empty(), "should be at least one input bit");
+    let n_segments = (bits.len() + SEGMENT_BITS - 1) / SEGMENT_BITS;
+    assert!(n_segments <= params.generators.len(), "not enough generators");
+
+    let k = Num::<J::Fs>::from(32);
+    let mut acc = EdwardsPointEx::zero();
+    for (segment, g) in bits.chunks(SEGMENT_BITS).zip(params.generators.iter()) {
+        let mut scalar = Num::ZERO;
+        for window in segment.chunks(WINDOW_BITS).rev() {
+            scalar = scalar * k + window_value(window);
+        }
+        acc = acc.add(&g.into_extended().mul(scalar, jubjub_params), jubjub_params);
+    }
+    acc.into_affine()
+}
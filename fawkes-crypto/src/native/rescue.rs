@@ -0,0 +1,108 @@
+use ff_uint::{
+    seedbox::{SeedBox, SeedBoxGen, SeedboxChaCha20},
+    Num, NumRepr, PrimeField,
+};
+use itertools::Itertools;
+
+use crate::native::mimc::pow;
+
+#[cfg(feature = "serde_support")]
+use crate::serde::{Deserialize, Serialize};
+
+// Rescue-Prime permutation of t elements with x^alpha and x^(1/alpha) S-boxes
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde_support",
+    serde(bound(serialize = "", deserialize = ""))
+)]
+pub struct RescueParams<Fr: PrimeField> {
+    pub c: Vec<Vec<Num<Fr>>>,
+    pub m: Vec<Vec<Num<Fr>>>,
+    pub t: usize,
+    pub rounds: usize,
+    pub alpha: u64,
+    pub alpha_inv: NumRepr<Fr::Inner>,
+}
+
+impl<Fr: PrimeField> RescueParams<Fr> {
+    pub fn new(t: usize, rounds: usize, alpha: u64) -> Self {
+        Self::new_with_salt(t, rounds, alpha, "")
+    }
+
+    // The number of rounds should be chosen according to the specification, the MDS matrix is the Cauchy one,
+    // as for Poseidon
+    pub fn new_with_salt(t: usize, rounds: usize, alpha: u64, salt: &str) -> Self {
+        assert!(t > 1, "state should be at least 2 elements");
+        let alpha_inv = inv_exponent::<Fr>(alpha);
+        let mut seedbox = SeedboxChaCha20::new_with_salt(
+            format!("fawkes_rescue(t={},rounds={},alpha={},salt={})", t, rounds, alpha, salt).as_bytes(),
+        );
+
+        let c = (0..2 * rounds)
+            .map(|_| (0..t).map(|_| seedbox.gen()).collect())
+            .collect();
+        let x = (0..t).map(|_| seedbox.gen()).collect::<Vec<Num<Fr>>>();
+        let y = (0..t).map(|_| seedbox.gen()).collect::<Vec<Num<Fr>>>();
+        let m = (0..t)
+            .map(|i| (0..t).map(|j| Num::ONE / (x[i] + y[j])).collect())
+            .collect();
+        RescueParams { c, m, t, rounds, alpha, alpha_inv }
+    }
+}
+
+// The inverse of alpha modulo r - 1, so that (x^alpha)^alpha_inv = x
+fn inv_exponent<Fr: PrimeField>(alpha: u64) -> NumRepr<Fr::Inner> {
+    assert!(alpha > 1, "alpha should be greater than 1");
+    let n = Num::<Fr>::MODULUS - NumRepr::ONE;
+    let a = NumRepr::from(alpha);
+    let (q, rem) = (n / a, n % a);
+    // alpha * alpha_inv = k * (r - 1) + 1 for some 0 < k < alpha, the product is split to avoid the overflow
+    (1..alpha)
+        .map(NumRepr::from)
+        .find(|k| (*k * rem + NumRepr::ONE) % a == NumRepr::ZERO)
+        .map(|k| k * q + (k * rem + NumRepr::ONE) / a)
+        .expect("alpha should be coprime with r - 1")
+}
+
+pub fn pow_inv<Fr: PrimeField>(a: Num<Fr>, params: &RescueParams<Fr>) -> Num<Fr> {
+    Num(a.0.pow(params.alpha_inv))
+}
+
+fn mix<Fr: PrimeField>(state: &mut [Num<Fr>], params: &RescueParams<Fr>) {
+    let new_state = params
+        .m
+        .iter()
+        .map(|row| row.iter().zip(state.iter()).fold(Num::ZERO, |acc, (m, s)| acc + *m * s))
+        .collect::<Vec<_>>();
+    state.copy_from_slice(&new_state);
+}
+
+fn ark<Fr: PrimeField>(state: &mut [Num<Fr>], c: &[Num<Fr>]) {
+    state.iter_mut().zip(c.iter()).for_each(|(s, c)| *s += c)
+}
+
+pub fn perm<Fr: PrimeField>(state: &mut [Num<Fr>], params: &RescueParams<Fr>) {
+    assert!(state.len() == params.t);
+    for i in 0..params.rounds {
+        state.iter_mut().for_each(|s| *s = pow(*s, params.alpha));
+        mix(state, params);
+        ark(state, &params.c[2 * i]);
+
+        state.iter_mut().for_each(|s| *s = pow_inv(*s, params));
+        mix(state, params);
+        ark(state, &params.c[2 * i + 1]);
+    }
+}
+
+// Sponge with the capacity of 1, the number of inputs is absorbed first, as in poseidon_sponge
+pub fn rescue_sponge<Fr: PrimeField>(inputs: &[Num<Fr>], params: &RescueParams<Fr>) -> Num<Fr> {
+    assert!(!inputs.is_empty(), "should be at least one input");
+    let mut state = vec![Num::ZERO; params.t];
+    let size = Num::from(inputs.len() as u64);
+    core::iter::once(&size).chain(inputs.iter()).chunks(params.t - 1).into_iter().for_each(|c| {
+        state.iter_mut().zip(c).for_each(|(l, r)| *l += *r);
+        perm(&mut state, params);
+    });
+    state[0]
+}
@@ -46,3 +46,275 @@ fn test_circuit_poseidon_merkle_root() {
     assert!(res, "Verifier result should be true");
 }
 
+
+#[cfg(feature = "heavy_tests")]
+fn test_circom_setup_prove_verify<E: engines::Engine>() {
+    use fawkes_crypto_zkbob::circuit::{circom::{read_r1cs, write_r1cs_cs}, cs::{BuildCS, CheckCS}};
+
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CNum<C>)) {
+        (&secret.0 * &secret.1).assert_eq(&public);
+    }
+
+    // circom circuit is emulated with exported circuit
+    let ref rcs = BuildCS::<E::Fr>::rc_new();
+    let signal_pub = CNum::alloc(rcs, None);
+    signal_pub.inputize();
+    circuit(signal_pub, Signal::alloc(rcs, None));
+    let mut data = vec![];
+    write_r1cs_cs(&mut data, &rcs.borrow()).unwrap();
+    let r1cs = read_r1cs::<E::Fr, _>(&mut &data[..]).unwrap();
+
+    let mut rng = thread_rng();
+    let (a, b) = (rng.gen(), rng.gen());
+    let ref rcs = CheckCS::<E::Fr>::rc_new();
+    let signal_pub = CNum::alloc(rcs, Some(&(a * b)));
+    signal_pub.inputize();
+    circuit(signal_pub, Signal::alloc(rcs, Some(&(a, b))));
+    let cs = rcs.borrow();
+    let witness = cs.values_input.iter().chain(cs.values_aux.iter()).cloned().collect::<Vec<_>>();
+
+    let params = circom::setup::<E>(&r1cs);
+    let (inputs, snark_proof) = circom::prove(&params, &r1cs, &witness).unwrap();
+    assert_eq!(inputs, vec![a * b]);
+    assert!(circom::verify(&params.get_vk(), &snark_proof, &inputs), "Verifier result should be true");
+}
+
+#[cfg(feature = "heavy_tests")]
+#[test]
+fn test_circom_bn256() {
+    test_circom_setup_prove_verify::<Bn256>();
+}
+
+#[cfg(feature = "heavy_tests")]
+#[test]
+fn test_circom_bls12_381() {
+    test_circom_setup_prove_verify::<engines::Bls12_381>();
+}
+
+#[cfg(feature = "heavy_tests")]
+#[test]
+fn test_prove_from_witness() {
+    use fawkes_crypto_zkbob::{BorshDeserialize, BorshSerialize, circuit::witness::Witness, ff_uint::Num};
+
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CNum<C>)) {
+        let poseidon_params = PoseidonParams::<C::Fr>::new(3, 8, 53);
+        let res = fawkes_crypto_zkbob::circuit::poseidon::c_poseidon(&[secret.0, secret.1], &poseidon_params);
+        res.assert_eq(&public);
+    }
+    let params = setup::<Bn256, _, _, _>(circuit);
+
+    let mut rng = thread_rng();
+    let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+    let secret: (Num<Fr>, Num<Fr>) = (rng.gen(), rng.gen());
+    let hash = fawkes_crypto_zkbob::native::poseidon::poseidon(&[secret.0, secret.1], &poseidon_params);
+
+    // the witness is transferred to the prover in serialized form
+    let witness = prover::build_witness(&params, &hash, &secret, circuit);
+    let data = witness.try_to_vec().unwrap();
+    let witness = Witness::<Fr>::try_from_slice(&data).unwrap();
+
+    let (inputs, snark_proof) = prover::prove_from_witness(&params, &witness);
+    assert_eq!(inputs, vec![hash]);
+
+    let res = verifier::verify(&params.get_vk(), &snark_proof, &inputs);
+    assert!(res, "Verifier result should be true");
+}
+
+#[cfg(feature = "heavy_tests")]
+#[test]
+fn test_setup_optimized() {
+    use fawkes_crypto_zkbob::backend::bellman_groth16::setup::setup_optimized;
+
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CMerkleProof<C, 8>)) {
+        let poseidon_params = PoseidonParams::<C::Fr>::new(3, 8, 53);
+        let res = c_poseidon_merkle_proof_root(&secret.0, &secret.1, &poseidon_params);
+        res.assert_eq(&public);
+    }
+    let params = setup_optimized::<Bn256, _, _, _>(circuit);
+
+    let mut rng = thread_rng();
+    let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+    let leaf = rng.gen();
+    let sibling = (0..8).map(|_| rng.gen()).collect::<SizedVec<_, 8>>();
+    let path = (0..8).map(|_| rng.gen()).collect::<SizedVec<bool, 8>>();
+    let proof = MerkleProof { sibling, path };
+    let root = poseidon_merkle_proof_root(leaf, &proof, &poseidon_params);
+
+    let (inputs, snark_proof) = prover::prove_checked(&params, &root, &(leaf, proof), circuit).unwrap();
+
+    let res = verifier::verify(&params.get_vk(), &snark_proof, &inputs);
+    assert!(res, "Verifier result should be true");
+
+    // the aux map is stored in the header only
+    let mut data = vec![];
+    params.write(&mut data).unwrap();
+    let mut res = Parameters::<Bn256>::read(&mut &data[..], false, true).unwrap();
+    assert_eq!(res.4, params.4);
+    assert!(params.write_legacy(&mut vec![]).is_err());
+
+    let aux_map = res.4.as_mut().unwrap();
+    aux_map.map[0] = aux_map.num_aux;
+    let mut data = vec![];
+    res.write(&mut data).unwrap();
+    match Parameters::<Bn256>::try_read(&mut &data[..], false, true) {
+        Err(ParametersError::AuxIndexOverflow { .. }) => {}
+        _ => panic!("aux index overflow should be detected"),
+    }
+}
+
+#[cfg(all(feature = "heavy_tests", feature = "multithread"))]
+#[test]
+fn test_concurrent_proofs() {
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CMerkleProof<C, 8>)) {
+        let poseidon_params = PoseidonParams::<C::Fr>::new(3, 8, 53);
+        let res = c_poseidon_merkle_proof_root(&secret.0, &secret.1, &poseidon_params);
+        res.assert_eq(&public);
+    }
+    let params = setup::<Bn256, _, _, _>(circuit);
+    let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+
+    // one Parameters is shared by all provers
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let mut rng = thread_rng();
+                let leaf = rng.gen();
+                let sibling = (0..8).map(|_| rng.gen()).collect::<SizedVec<_, 8>>();
+                let path = (0..8).map(|_| rng.gen()).collect::<SizedVec<bool, 8>>();
+                let proof = MerkleProof { sibling, path };
+                let root = poseidon_merkle_proof_root(leaf, &proof, &poseidon_params);
+
+                let (inputs, snark_proof) = prover::prove(&params, &root, &(leaf, proof), circuit);
+                assert!(verifier::verify(&params.get_vk(), &snark_proof, &inputs), "Verifier result should be true");
+            });
+        }
+    });
+}
+
+#[cfg(feature = "heavy_tests")]
+#[test]
+fn test_parameters_format() {
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CNum<C>)) {
+        (&secret.0 * &secret.1).assert_eq(&public);
+    }
+    let params = setup::<Bn256, _, _, _>(circuit);
+
+    let mut data = vec![];
+    params.write(&mut data).unwrap();
+    assert_eq!(&data[..4], PARAMETERS_MAGIC);
+    let res = Parameters::<Bn256>::read(&mut &data[..], false, true).unwrap();
+    assert_eq!(res.1, params.1);
+    assert_eq!(res.2, params.2);
+    assert_eq!(res.3, params.3);
+    assert_eq!(res.0.vk.ic.len(), params.0.vk.ic.len());
+    // without the check the gates are not decompressed
+    let res = Parameters::<Bn256>::try_read(&mut &data[..], false, false).unwrap();
+    assert_eq!(res.1, params.1);
+
+    // legacy layout without the header
+    let mut legacy = vec![];
+    params.write_legacy(&mut legacy).unwrap();
+    let res = Parameters::<Bn256>::read(&mut &legacy[..], false, true).unwrap();
+    assert_eq!(res.2, params.2);
+
+    match Parameters::<engines::Bls12_381>::try_read(&mut &data[..], false, true) {
+        Err(ParametersError::WrongEngine { expected: 2, found: 1 }) => {}
+        _ => panic!("engine mismatch should be detected"),
+    }
+
+    match Parameters::<Bn256>::try_read(&mut &data[..data.len() / 2], false, true) {
+        Err(ParametersError::Io(_)) => {}
+        _ => panic!("truncated parameters should be detected"),
+    }
+    assert!(Parameters::<Bn256>::read(&mut &data[..data.len() / 2], false, true).is_err());
+
+    // corrupt the last byte of the compressed gates, which are written right after the header
+    let mut corrupted = data.clone();
+    let header_len = corrupted.len() - legacy.len();
+    let gates_end = header_len + 4 + 4 + params.2.len();
+    corrupted[gates_end - 1] ^= 1;
+    match Parameters::<Bn256>::try_read(&mut &corrupted[..], false, false) {
+        Err(ParametersError::WrongHash) => {}
+        _ => panic!("corrupted gates should be detected"),
+    }
+}
+
+#[cfg(feature = "heavy_tests")]
+#[test]
+fn test_fallible_prove_verify() {
+    use fawkes_crypto_zkbob::{circuit::witness::Witness, ff_uint::Num};
+
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CNum<C>)) {
+        (&secret.0 * &secret.1).assert_eq(&public);
+    }
+    let params = setup::<Bn256, _, _, _>(circuit);
+    let vk = params.get_vk();
+
+    let (a, b): (Num<Fr>, Num<Fr>) = (Num::from(3), Num::from(5));
+    let (inputs, mut snark_proof) = prover::try_prove(&params, &(a * b), &(a, b), circuit).unwrap();
+    assert!(verifier::try_verify(&vk, &snark_proof, &inputs).unwrap());
+
+    match verifier::try_verify(&vk, &snark_proof, &[]) {
+        Err(FawkesError::WrongNumInputs { expected: 1, found: 0 }) => {}
+        _ => panic!("wrong number of inputs should be detected"),
+    }
+
+    let mut empty_vk = params.get_vk();
+    empty_vk.ic.clear();
+    match verifier::try_verify(&empty_vk, &snark_proof, &inputs) {
+        Err(FawkesError::WrongNumInputs { expected: 0, found: 1 }) => {}
+        _ => panic!("malformed verifying key should be detected"),
+    }
+
+    match prover::prove_checked(&params, &(a * b + Num::ONE), &(a, b), circuit) {
+        Err(FawkesError::Unsatisfied { .. }) => {}
+        _ => panic!("unsatisfied gate should be detected"),
+    }
+
+    // (1, 1) is not on the curve
+    snark_proof.a = group::G1Point(Num::ONE, Num::ONE);
+    match verifier::try_verify(&vk, &snark_proof, &inputs) {
+        Err(FawkesError::InvalidPoint(_)) => {}
+        _ => panic!("invalid point should be detected"),
+    }
+
+    let witness = Witness { values_input: vec![Num::ONE], values_aux: vec![] };
+    match prover::try_prove_from_witness(&params, &witness) {
+        Err(FawkesError::WrongNumInputs { expected: 2, found: 1 }) => {}
+        _ => panic!("wrong witness should be detected"),
+    }
+
+    let witness = Witness { values_input: vec![Num::ONE, a * b], values_aux: vec![a] };
+    match prover::try_prove_from_witness(&params, &witness) {
+        Err(FawkesError::WrongNumAux { expected: 4, found: 1 }) => {}
+        _ => panic!("wrong witness should be detected"),
+    }
+}
+
+#[cfg(feature = "heavy_tests")]
+#[test]
+fn test_single_input() {
+    use fawkes_crypto_zkbob::{
+        circuit::{bool::CBool, cs::{CheckCS, RCS}},
+        ff_uint::Num,
+    };
+
+    #[derive(Clone, Signal)]
+    #[GenerateValue = "Input"]
+    pub struct CInput<C: CS> {
+        pub a: CNum<C>,
+        #[public]
+        pub product: CNum<C>,
+        pub b: CNum<C>,
+    }
+
+    fn circuit<C:CS>(input: CInput<C>) {
+        (&input.a * &input.b).assert_eq(&input.product);
+    }
+    let params = setup::setup_single::<Bn256, _, _>(circuit);
+
+    let value = Input::<Fr> { a: Num::from(3), product: Num::from(15), b: Num::from(5) };
+    let (inputs, snark_proof) = prover::prove_single(&params, &value, circuit);
+    assert_eq!(inputs, verifier::public_inputs::<CheckCS<Fr>, CInput<_>>(&(Num::from(15),)));
+    assert!(verifier::verify(&params.get_vk(), &snark_proof, &inputs));
+}
@@ -0,0 +1,56 @@
+use fawkes_crypto_zkbob::{
+    circuit::{analysis::analyze, bool::CBool, cs::CS, num::CNum, poseidon::{c_poseidon_merkle_proof_root, CMerkleProof}},
+    core::signal::Signal,
+    engines::bn256::Fr,
+    native::poseidon::PoseidonParams,
+};
+
+#[test]
+fn test_analysis_merkle_proof() {
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CMerkleProof<C, 8>)) {
+        let poseidon_params = PoseidonParams::<C::Fr>::new(3, 8, 53);
+        let res = c_poseidon_merkle_proof_root(&secret.0, &secret.1, &poseidon_params);
+        res.assert_eq(&public);
+    }
+
+    let report = analyze::<Fr, _, _, _>(circuit);
+    assert!(report.is_ok(), "unexpected issues: {:?}", report);
+}
+
+#[test]
+fn test_analysis_unused_aux() {
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CNum<C>)) {
+        (&secret.0 * &secret.0).assert_eq(&public);
+    }
+
+    let report = analyze::<Fr, _, _, _>(circuit);
+    // public signal is aux 0, secret signals are aux 1 and 2
+    assert_eq!(report.unused_aux, vec![2]);
+    assert!(report.single_linear.is_empty());
+    assert!(report.unchecked_bits.is_empty());
+}
+
+#[test]
+fn test_analysis_single_linear() {
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CNum<C>)) {
+        (&secret.0 * &secret.0).assert_eq(&public);
+        (&secret.0 + &secret.1).assert_zero();
+    }
+
+    let report = analyze::<Fr, _, _, _>(circuit);
+    assert!(report.unused_aux.is_empty());
+    assert_eq!(report.single_linear.len(), 1);
+    assert_eq!(report.single_linear[0].0, 2);
+}
+
+#[test]
+fn test_analysis_unchecked_bits() {
+    fn circuit<C:CS>(public: CNum<C>, secret: (CNum<C>, CNum<C>)) {
+        let checked: CBool<C> = secret.0.to_bool();
+        let unchecked: CBool<C> = secret.1.to_bool_unchecked();
+        (checked & unchecked).to_num().assert_eq(&public);
+    }
+
+    let report = analyze::<Fr, _, _, _>(circuit);
+    assert_eq!(report.unchecked_bits, vec![2]);
+}
@@ -0,0 +1,137 @@
+use fawkes_crypto_zkbob::{
+    circuit::{bitify::*, cs::{CheckCS, DebugCS, CS}, num::CNum},
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::Num,
+    rand::{thread_rng, Rng},
+};
+
+const LIMIT: usize = 32;
+
+fn gates<C: CS>(cs: &C) -> usize {
+    cs.num_gates()
+}
+
+#[test]
+fn test_comparators() {
+    let mut rng = thread_rng();
+    for _ in 0..10 {
+        let (a, b) = (rng.gen::<u16>() as u64, rng.gen::<u16>() as u64);
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let ca = CNum::alloc(cs, Some(&Num::from(a)));
+        let cb = CNum::alloc(cs, Some(&Num::from(b)));
+
+        assert_eq!(c_lt(&ca, &cb, LIMIT).get_value().unwrap(), a < b);
+        assert_eq!(c_le(&ca, &cb, LIMIT).get_value().unwrap(), a <= b);
+        assert_eq!(c_gt(&ca, &cb, LIMIT).get_value().unwrap(), a > b);
+        assert_eq!(c_ge(&ca, &cb, LIMIT).get_value().unwrap(), a >= b);
+        assert!(c_le(&ca, &ca, LIMIT).get_value().unwrap());
+
+        let ct = Num::from(b);
+        assert_eq!(c_lt_constant(&ca, ct, LIMIT).get_value().unwrap(), a < b);
+        assert_eq!(c_le_constant(&ca, ct, LIMIT).get_value().unwrap(), a <= b);
+        assert_eq!(c_gt_constant(&ca, ct, LIMIT).get_value().unwrap(), a > b);
+        assert_eq!(c_ge_constant(&ca, ct, LIMIT).get_value().unwrap(), a >= b);
+
+        assert_eq!(c_min(&ca, &cb, LIMIT).get_value().unwrap(), Num::from(a.min(b)));
+        assert_eq!(c_max(&ca, &cb, LIMIT).get_value().unwrap(), Num::from(a.max(b)));
+
+        assert!(cs.borrow().check().is_ok());
+    }
+}
+
+#[test]
+fn test_comparators_constraints() {
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(5)));
+    let b = CNum::alloc(cs, Some(&Num::from(7)));
+
+    let n = gates(&*cs.borrow());
+    assert!(c_lt(&a, &b, LIMIT).get_value().unwrap());
+    assert_eq!(gates(&*cs.borrow()) - n, LIMIT + 1);
+
+    let n = gates(&*cs.borrow());
+    assert!(c_ge_constant(&b, Num::from(7), LIMIT).get_value().unwrap());
+    assert_eq!(gates(&*cs.borrow()) - n, LIMIT + 1);
+
+    // one more gate for the switch
+    let n = gates(&*cs.borrow());
+    assert_eq!(c_min(&a, &b, LIMIT).get_value().unwrap(), Num::from(5));
+    assert_eq!(gates(&*cs.borrow()) - n, LIMIT + 2);
+}
+
+#[test]
+fn test_comparators_constant_folding() {
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(5)));
+    let ca: CNum<_> = a.derive_const(&Num::from(3));
+    let cb: CNum<_> = a.derive_const(&Num::from(9));
+    let n = gates(&*cs.borrow());
+
+    assert_eq!(c_lt(&ca, &cb, LIMIT).as_const(), Some(true));
+    assert_eq!(c_ge(&ca, &cb, LIMIT).as_const(), Some(false));
+    assert_eq!(c_max(&ca, &cb, LIMIT).as_const(), Some(Num::from(9)));
+
+    // the constant is out of the limit or zero
+    assert_eq!(c_lt_constant(&a, Num::ZERO, LIMIT).as_const(), Some(false));
+    assert_eq!(c_ge_constant(&a, Num::ZERO, LIMIT).as_const(), Some(true));
+    assert_eq!(c_lt_constant(&a, Num::from(1u64 << LIMIT), LIMIT).as_const(), Some(true));
+    assert_eq!(c_le_constant(&a, Num::from((1u64 << LIMIT) - 1), LIMIT).as_const(), Some(true));
+    assert_eq!(c_gt_constant(&a, Num::from((1u64 << LIMIT) - 1), LIMIT).as_const(), Some(false));
+
+    c_assert_range(&ca, 2);
+    c_assert_in_interval(&cb, Num::from(9), Num::from(9));
+
+    assert_eq!(gates(&*cs.borrow()), n);
+}
+
+#[test]
+fn test_assert_range() {
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(255)));
+    let n = gates(&*cs.borrow());
+    c_assert_range(&a, 8);
+    assert_eq!(gates(&*cs.borrow()) - n, 8);
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(256)));
+    c_assert_range(&a, 8);
+    assert!(cs.borrow().check().is_err());
+
+    // zero bits allow only zero
+    for (value, ok) in [(0, true), (1, false)] {
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let a = CNum::alloc(cs, Some(&Num::from(value)));
+        c_assert_range(&a, 0);
+        assert_eq!(cs.borrow().check().is_ok(), ok, "value {}", value);
+    }
+}
+
+#[test]
+fn test_assert_in_interval() {
+    for (value, ok) in [(9, false), (10, true), (15, true), (20, true), (21, false)] {
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let a = CNum::alloc(cs, Some(&Num::from(value)));
+        c_assert_in_interval(&a, Num::from(10), Num::from(20));
+        assert_eq!(cs.borrow().check().is_ok(), ok, "value {}", value);
+    }
+
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(12)));
+    let n = gates(&*cs.borrow());
+    c_assert_in_interval(&a, Num::from(10), Num::from(20));
+    assert_eq!(gates(&*cs.borrow()) - n, 8);
+
+    // the upper bound is implied by the range check of the width 2^4 - 1
+    let n = gates(&*cs.borrow());
+    c_assert_in_interval(&a, Num::from(10), Num::from(25));
+    assert_eq!(gates(&*cs.borrow()) - n, 4);
+
+    // the interval of one value
+    for (value, ok) in [(9, false), (10, true), (11, false)] {
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let a = CNum::alloc(cs, Some(&Num::from(value)));
+        c_assert_in_interval(&a, Num::from(10), Num::from(10));
+        assert_eq!(cs.borrow().check().is_ok(), ok, "value {}", value);
+    }
+}
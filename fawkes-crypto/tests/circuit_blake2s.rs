@@ -0,0 +1,91 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        bitify::c_into_bits_le,
+        blake2s::c_blake2s,
+        bool::CBool,
+        cs::{CheckCS, DebugCS, CS},
+        num::CNum,
+    },
+    constants::PERSONALIZATION,
+    core::signal::Signal,
+    engines::bn256::{Fr, Fs},
+    ff_uint::Num,
+    native::{
+        bitify::{bits_le_to_bytes, bytes_to_bits_le},
+        blake2s::blake2s,
+    },
+    rand::{thread_rng, Rng},
+};
+
+mod common;
+use common::{alloc_bits, bit_values, hex, num_gates};
+
+#[test]
+fn test_native_blake2s() {
+    // the zero personalization gives the plain Blake2s-256
+    assert_eq!(
+        hex(&blake2s(b"abc", &[0; 8])),
+        "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+    );
+    assert_ne!(blake2s(b"abc", &[0; 8]), blake2s(b"abc", PERSONALIZATION));
+    assert_eq!(
+        hex(&blake2s(b"", &[0; 8])),
+        "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9"
+    );
+}
+
+#[test]
+fn test_circuit_blake2s_empty() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    for personalization in [&[0; 8], PERSONALIZATION] {
+        let res = c_blake2s(cs, &[], personalization);
+        // the hash of the empty message is constant
+        assert!(res.iter().all(|b| b.as_const().is_some()));
+        assert_eq!(bits_le_to_bytes(&bit_values(&res)), blake2s(&[], personalization).to_vec());
+    }
+    assert_eq!(cs.borrow().num_gates(), 0);
+}
+
+#[test]
+fn test_circuit_blake2s() {
+    let mut rng = thread_rng();
+    // the lengths around the block boundary
+    for len in [1, 32, 63, 64, 65, 130] {
+        let data = (0..len).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let bits = alloc_bits(cs, &bytes_to_bits_le(&data));
+        let res = bit_values(&c_blake2s(cs, &bits, PERSONALIZATION));
+        assert_eq!(bits_le_to_bytes(&res), blake2s(&data, PERSONALIZATION).to_vec(), "len {}", len);
+        assert!(cs.borrow().check().is_ok());
+    }
+}
+
+// The same input as of hash_r in eddsaposeidon: the little endian bytes of the secret key and the message
+#[test]
+fn test_circuit_blake2s_hash_r() {
+    let mut rng = thread_rng();
+    let sk: Num<Fs> = rng.gen();
+    let m: Num<Fr> = rng.gen();
+    let mut data = vec![];
+    sk.to_uint().as_inner().as_ref().iter().for_each(|x| data.extend_from_slice(&x.to_le_bytes()));
+    m.to_uint().as_inner().as_ref().iter().for_each(|x| data.extend_from_slice(&x.to_le_bytes()));
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let zero: CBool<_> = CBool::from_const(cs, &false);
+    let mut bits = vec![];
+    for n in [CNum::alloc(cs, Some(&sk.to_other().unwrap())), CNum::alloc(cs, Some(&m))] {
+        bits.extend(c_into_bits_le(&n, 254));
+        bits.extend([zero.clone(), zero.clone()]);
+    }
+    let res = bit_values(&c_blake2s(cs, &bits, PERSONALIZATION));
+    assert_eq!(bits_le_to_bytes(&res), blake2s(&data, PERSONALIZATION).to_vec());
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_circuit_blake2s_constraints() {
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let bits = alloc_bits(cs, &[false; 512]);
+    // one block of 10 rounds
+    assert_eq!(num_gates(cs, || c_blake2s(cs, &bits, PERSONALIZATION)), 21280);
+}
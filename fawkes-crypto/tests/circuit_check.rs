@@ -0,0 +1,77 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::{BuildCS, CheckCS, WitnessCS, CS, RCS},
+        gates::{check_gates, GateSource},
+        num::CNum,
+        poseidon::c_poseidon,
+        scope::Scope,
+    },
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::Num,
+    native::poseidon::{poseidon, PoseidonParams},
+    rand::{thread_rng, Rng},
+};
+
+#[test]
+fn test_check_satisfied() {
+    let mut rng = thread_rng();
+    let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+    let data: [Num<Fr>; 2] = [rng.gen(), rng.gen()];
+
+    let ref cs = CheckCS::rc_new();
+    let inputs = [CNum::alloc(cs, Some(&data[0])), CNum::alloc(cs, Some(&data[1]))];
+    let res = c_poseidon(&inputs, &poseidon_params);
+    res.inputize();
+
+    assert!(cs.borrow().check().is_ok());
+    assert!(res.get_value().unwrap() == poseidon(&data, &poseidon_params));
+}
+
+#[test]
+fn test_check_unsatisfied() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(3)));
+    let b = CNum::alloc(cs, Some(&Num::from(5)));
+    (&a * &b).assert_const(&Num::from(15));
+
+    cs.borrow_mut().push_scope("outer");
+    cs.borrow_mut().push_scope("inner");
+    let c = CNum::alloc(cs, Some(&Num::from(7)));
+    c.assert_eq(&a);
+    cs.borrow_mut().pop_scope();
+    cs.borrow_mut().pop_scope();
+
+    let err = cs.borrow().check().unwrap_err();
+    assert_eq!(err.index, 2);
+    assert!(err.a == Num::from(7));
+    assert!(err.b == Num::ONE);
+    assert!(err.c == Num::from(3));
+    assert_eq!(err.scope.as_deref(), Some("outer/inner"));
+}
+
+fn circuit<C: CS>(cs: &RCS<C>, a: Option<Num<C::Fr>>, b: Option<Num<C::Fr>>) {
+    let a = CNum::alloc(cs, a.as_ref());
+    a.inputize();
+    let b = CNum::alloc(cs, b.as_ref());
+    let _scope = Scope::new(cs, "square");
+    b.square().assert_eq(&a);
+}
+
+#[test]
+fn test_witness_scopes() {
+    let ref rcs = BuildCS::<Fr>::rc_new();
+    circuit(rcs, None, None);
+    let cs = rcs.borrow();
+
+    // the witness does not satisfy the gates of the setup
+    let ref wrcs = WitnessCS::rc_new(cs.gates.len(), GateSource::Precomputed(&cs.gates), &cs.const_tracker);
+    wrcs.borrow_mut().record_scopes();
+    circuit(wrcs, Some(Num::from(10)), Some(Num::from(3)));
+
+    let wcs = wrcs.borrow();
+    let err = check_gates(wcs.get_gate_iterator(), &wcs.values_input, &wcs.values_aux).unwrap_err();
+    assert_eq!(err.index, 2);
+    assert_eq!(wcs.get_gate_scope(err.index), Some("square"));
+    assert_eq!(wcs.get_gate_scope(0), cs.get_gate_scope(0));
+}
@@ -0,0 +1,185 @@
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use fawkes_crypto_zkbob::{
+    circuit::{
+        circom::{read_r1cs, read_wtns, wire_id, write_r1cs_cs, write_sym, R1cs},
+        cs::BuildCS,
+        num::CNum,
+        scope::Scope,
+    },
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::{Num, NumRepr, PrimeFieldParams, Uint},
+};
+use std::io::{ErrorKind, Read, Write};
+
+fn build() -> BuildCS<Fr> {
+    let ref rcs = BuildCS::rc_new();
+    let public = CNum::alloc(rcs, None);
+    public.inputize();
+    let secret: (CNum<_>, CNum<_>) = Signal::alloc(rcs, None);
+    {
+        let _scope = Scope::new(rcs, "square");
+        (&secret.0 * &secret.0).assert_eq(&public);
+    }
+    (&secret.0 + &secret.1).assert_zero();
+    let cs = rcs.borrow().clone();
+    cs
+}
+
+fn read_lc<R: Read>(r: &mut R) -> Vec<(u32, Num<Fr>)> {
+    let n = r.read_u32::<LittleEndian>().unwrap();
+    (0..n)
+        .map(|_| {
+            let wire = r.read_u32::<LittleEndian>().unwrap();
+            let mut buf = [0u8; 32];
+            r.read_exact(&mut buf).unwrap();
+            let k = Num::from_uint(NumRepr(Uint::from_little_endian(&buf))).unwrap();
+            (wire, k)
+        })
+        .collect()
+}
+
+#[test]
+fn test_circom_r1cs_export() {
+    let cs = build();
+    let mut data = vec![];
+    write_r1cs_cs(&mut data, &cs).unwrap();
+
+    let r = &mut &data[..];
+    let mut magic = [0u8; 4];
+    r.read_exact(&mut magic).unwrap();
+    assert_eq!(&magic, b"r1cs");
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap(), 1);
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap(), 3);
+
+    // header
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap(), 1);
+    assert_eq!(r.read_u64::<LittleEndian>().unwrap(), 64);
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap(), 32);
+    let mut prime = [0u8; 32];
+    r.read_exact(&mut prime).unwrap();
+    assert_eq!(prime.to_vec(), Fr::MODULUS.to_little_endian());
+    let num_wires = cs.num_input + cs.num_aux;
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap() as usize, num_wires);
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap(), 0);
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap() as usize, cs.num_input - 1);
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap(), 0);
+    assert_eq!(r.read_u64::<LittleEndian>().unwrap() as usize, num_wires);
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap() as usize, cs.gates.len());
+
+    // constraints
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap(), 2);
+    let size = r.read_u64::<LittleEndian>().unwrap() as usize;
+    let (mut section, rest) = r.split_at(size);
+    *r = rest;
+    for g in cs.gates.iter() {
+        for lc in [&g.0, &g.1, &g.2].iter() {
+            let expected = lc
+                .iter()
+                .map(|(k, i)| (wire_id(*i, cs.num_input), *k))
+                .collect::<Vec<_>>();
+            assert_eq!(read_lc(&mut section), expected);
+        }
+    }
+    assert!(section.is_empty());
+
+    // wire2label
+    assert_eq!(r.read_u32::<LittleEndian>().unwrap(), 3);
+    assert_eq!(r.read_u64::<LittleEndian>().unwrap() as usize, 8 * num_wires);
+    for i in 0..num_wires as u64 {
+        assert_eq!(r.read_u64::<LittleEndian>().unwrap(), i);
+    }
+    assert!(r.is_empty());
+}
+
+#[test]
+fn test_circom_sym_export() {
+    let cs = build();
+    let mut data = vec![];
+    write_sym(&mut data, &cs).unwrap();
+    let sym = String::from_utf8(data).unwrap();
+    let lines = sym.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), cs.num_input - 1 + cs.num_aux);
+    assert_eq!(lines[0], "1,1,0,main.input_1");
+    // the square multiplication result is allocated inside the scope
+    assert!(lines.iter().any(|l| l.contains("main.square.aux_")));
+    assert!(lines.iter().any(|l| l.ends_with(",main.aux_0")));
+}
+
+#[test]
+fn test_circom_r1cs_import() {
+    let cs = build();
+    let mut data = vec![];
+    write_r1cs_cs(&mut data, &cs).unwrap();
+
+    let r1cs = read_r1cs::<Fr, _>(&mut &data[..]).unwrap();
+    assert_eq!(r1cs.num_input, cs.num_input);
+    assert_eq!(r1cs.num_aux, cs.num_aux);
+    assert_eq!(r1cs.gates.len(), cs.gates.len());
+    for (a, b) in r1cs.gates.iter().zip(cs.gates.iter()) {
+        assert_eq!(a.0, b.0);
+        assert_eq!(a.1, b.1);
+        assert_eq!(a.2, b.2);
+    }
+
+    data[0] = b'x';
+    assert!(read_r1cs::<Fr, _>(&mut &data[..]).is_err());
+}
+
+#[test]
+fn test_circom_r1cs_import_huge_counts() {
+    let cs = build();
+    let mut data = vec![];
+    write_r1cs_cs(&mut data, &cs).unwrap();
+
+    // number of constraints in the header section
+    let mut gates_overflow = data.clone();
+    gates_overflow[84..88].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = read_r1cs::<Fr, _>(&mut &gates_overflow[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    // size of the first linear combination in the constraints section
+    let mut lc_overflow = data.clone();
+    lc_overflow[100..104].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = read_r1cs::<Fr, _>(&mut &lc_overflow[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_circom_wtns_import() {
+    let values: [Num<Fr>; 3] = [Num::ONE, Num::from(3), Num::from(5)];
+
+    let mut header = vec![];
+    header.write_u32::<LittleEndian>(32).unwrap();
+    header.write_all(&Fr::MODULUS.to_little_endian()).unwrap();
+    header.write_u32::<LittleEndian>(values.len() as u32).unwrap();
+    let mut witness = vec![];
+    for v in values.iter() {
+        witness.write_all(&v.to_uint().0.to_little_endian()).unwrap();
+    }
+
+    let mut data = vec![];
+    data.write_all(b"wtns").unwrap();
+    data.write_u32::<LittleEndian>(2).unwrap();
+    data.write_u32::<LittleEndian>(2).unwrap();
+    for (t, section) in [(1, &header), (2, &witness)].iter() {
+        data.write_u32::<LittleEndian>(*t).unwrap();
+        data.write_u64::<LittleEndian>(section.len() as u64).unwrap();
+        data.write_all(section).unwrap();
+    }
+
+    let res = read_wtns::<Fr, _>(&mut &data[..]).unwrap();
+    assert_eq!(res, values.to_vec());
+
+    // the number of witness values in the header exceeds the witness section
+    let mut overflow = data.clone();
+    overflow[60..64].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = read_wtns::<Fr, _>(&mut &overflow[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let r1cs = R1cs::<Fr> { num_input: 2, num_aux: 1, gates: vec![] };
+    let witness = r1cs.split_witness(&res).unwrap();
+    assert_eq!(witness.values_input, values[..2].to_vec());
+    assert_eq!(witness.values_aux, values[2..].to_vec());
+}
@@ -0,0 +1,105 @@
+use fawkes_crypto_zkbob::{
+    circuit::{cs::{CheckCS, DebugCS, CS}, divrem::c_div_rem, num::CNum, uint::CUint},
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::{Num, NumRepr},
+    native::divrem::div_rem,
+    rand::{thread_rng, Rng},
+};
+
+const LIMIT: usize = 64;
+
+#[test]
+fn test_native_div_rem() {
+    let (q, r) = div_rem(Num::<Fr>::from(100), Num::from(7));
+    assert_eq!((q, r), (Num::from(14), Num::from(2)));
+    let (q, r) = div_rem(Num::<Fr>::from(5), Num::from(9));
+    assert_eq!((q, r), (Num::ZERO, Num::from(5)));
+}
+
+#[test]
+fn test_div_rem() {
+    let mut rng = thread_rng();
+    for _ in 0..10 {
+        let a = Num::<Fr>::from(rng.gen::<u64>());
+        let b = Num::from(rng.gen::<u32>() as u64 + 1);
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let ca = CNum::alloc(cs, Some(&a));
+        let cb = CNum::alloc(cs, Some(&b));
+
+        let (q, r) = c_div_rem(&ca, &cb, LIMIT);
+        assert_eq!((q.get_value().unwrap(), r.get_value().unwrap()), div_rem(a, b));
+
+        // constant divisor
+        let (q, r) = c_div_rem(&ca, &ca.derive_const(&b), LIMIT);
+        assert_eq!((q.get_value().unwrap(), r.get_value().unwrap()), div_rem(a, b));
+
+        assert!(cs.borrow().check().is_ok());
+    }
+}
+
+#[test]
+fn test_div_rem_trivial_constant() {
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(1000)));
+
+    // division by one is free
+    let n = cs.borrow().num_gates();
+    let (q, r) = c_div_rem(&a, &a.derive_const(&Num::ONE), LIMIT);
+    assert_eq!((q.get_value(), r.as_const()), (Some(Num::from(1000)), Some(Num::ZERO)));
+    assert_eq!(cs.borrow().num_gates(), n);
+
+    // the divisor is larger than any dividend, only the dividend is range checked
+    let n = cs.borrow().num_gates();
+    let (q, r) = c_div_rem(&a, &a.derive_const(&Num::from(1u128 << LIMIT)), LIMIT);
+    assert_eq!((q.as_const(), r.get_value()), (Some(Num::ZERO), Some(Num::from(1000))));
+    assert_eq!(cs.borrow().num_gates() - n, LIMIT);
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(1u128 << LIMIT)));
+    c_div_rem(&a, &a.derive_const(&Num::from(1u128 << LIMIT)), LIMIT);
+    assert!(cs.borrow().check().is_err());
+}
+
+#[test]
+fn test_div_rem_zero_divisor() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(10)));
+    let b = CNum::alloc(cs, Some(&Num::ZERO));
+    c_div_rem(&a, &b, LIMIT);
+    assert!(cs.borrow().check().is_err());
+}
+
+#[test]
+fn test_div_rem_constraints() {
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let a = CNum::alloc(cs, Some(&Num::from(1000)));
+    let b = CNum::alloc(cs, Some(&Num::from(30)));
+
+    // range checks of q and r, the product, the comparison and its assertion
+    let n = cs.borrow().num_gates();
+    c_div_rem(&a, &b, LIMIT);
+    assert_eq!(cs.borrow().num_gates() - n, 3 * LIMIT + 3);
+
+    // the remainder of the power of two divisor is checked by one range check
+    let n = cs.borrow().num_gates();
+    c_div_rem(&a, &a.derive_const(&Num::from(1u64 << 16)), LIMIT);
+    assert_eq!(cs.borrow().num_gates() - n, (LIMIT - 16) + 16 + 1);
+
+    // both values are constant
+    let n = cs.borrow().num_gates();
+    let (q, r) = c_div_rem(&a.derive_const(&Num::from(1000)), &b.derive_const(&Num::from(30)), LIMIT);
+    assert_eq!((q.as_const(), r.as_const()), (Some(Num::from(33)), Some(Num::from(10))));
+    assert_eq!(cs.borrow().num_gates(), n);
+}
+
+#[test]
+fn test_uint_div_rem() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CUint::<_, 32>::alloc(cs, Some(&NumRepr::from(1000)));
+    let b = CUint::<_, 32>::alloc(cs, Some(&NumRepr::from(30)));
+    let (q, r) = a.div_rem(&b);
+    assert_eq!(q.get_value(), Some(NumRepr::from(33)));
+    assert_eq!(r.get_value(), Some(NumRepr::from(10)));
+    assert!(cs.borrow().check().is_ok());
+}
@@ -0,0 +1,155 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        bool::CBool,
+        cs::{BuildCS, CheckCS, CS},
+        discriminant::CDiscriminant,
+        num::CNum,
+    },
+    core::signal::{enum_signal, Signal},
+    engines::bn256::Fr,
+    ff_uint::{Num, PrimeField},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tx<Fr: PrimeField> {
+    Deposit(Num<Fr>),
+    Transfer { from: Num<Fr>, to: Num<Fr> },
+    Noop,
+}
+
+#[enum_signal]
+#[derive(Clone)]
+#[Value = "Tx<C::Fr>"]
+pub enum CTx<C: CS> {
+    Deposit(CNum<C>),
+    Transfer { from: CNum<C>, to: CNum<C> },
+    Noop,
+}
+
+#[enum_signal(binary)]
+#[derive(Clone)]
+#[Value = "Tx<C::Fr>"]
+pub enum CTxBinary<C: CS> {
+    Deposit(CNum<C>),
+    Transfer { from: CNum<C>, to: CNum<C> },
+    Noop,
+}
+
+// the derive keeps the enum and generates CTxDerivedSignal, the CS parameter could have any name
+#[derive(Signal)]
+#[Value = "Tx<S::Fr>"]
+#[Discriminant = "binary"]
+pub enum CTxDerived<S: CS> {
+    Deposit(CNum<S>),
+    Transfer { from: CNum<S>, to: CNum<S> },
+    Noop,
+}
+
+// the variant named as the discriminant does not conflict with it
+#[enum_signal]
+#[derive(Clone)]
+#[Value = "Kind<C::Fr>"]
+pub enum CKind<C: CS> {
+    Discriminant(CNum<C>),
+    Other(CNum<C>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Kind<Fr: PrimeField> {
+    Discriminant(Num<Fr>),
+    Other(Num<Fr>),
+}
+
+fn values() -> Vec<Tx<Fr>> {
+    vec![
+        Tx::Deposit(Num::from(7)),
+        Tx::Transfer { from: Num::from(1), to: Num::from(2) },
+        Tx::Noop,
+    ]
+}
+
+macro_rules! test_roundtrip {
+    ($signal:ident) => {
+        let ref bcs = BuildCS::<Fr>::rc_new();
+        let _: $signal<_> = Signal::alloc(bcs, None);
+
+        for value in values() {
+            let ref cs = CheckCS::rc_new();
+            let signal: $signal<_> = Signal::alloc(cs, Some(&value));
+            assert_eq!(signal.get_value(), Some(value.clone()));
+            assert!(cs.borrow().check().is_ok());
+
+            // the layout of the circuit does not depend on the variant
+            assert_eq!(cs.borrow().num_aux(), bcs.borrow().num_aux);
+            assert_eq!(cs.borrow().num_gates(), bcs.borrow().gates.len());
+
+            let c: $signal<_> = Signal::from_const(cs, &value);
+            assert_eq!(c.as_const(), Some(value.clone()));
+            assert!(signal.is_eq(&c).get_value().unwrap());
+            signal.assert_const(&value);
+            signal.assert_eq(&c);
+            assert!(cs.borrow().check().is_ok());
+        }
+    };
+}
+
+#[test]
+fn test_enum_one_hot() {
+    test_roundtrip!(CTx);
+    assert_eq!(CDiscriminant::<CheckCS<Fr>, 3, false>::num_bits(), 3);
+}
+
+#[test]
+fn test_enum_binary() {
+    test_roundtrip!(CTxBinary);
+    assert_eq!(CDiscriminant::<CheckCS<Fr>, 3, true>::num_bits(), 2);
+}
+
+#[test]
+fn test_enum_derive() {
+    test_roundtrip!(CTxDerivedSignal);
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let signal: CTxDerivedSignal<_> = Signal::alloc(cs, Some(&values()[2]));
+    assert!(signal.is_noop().get_value().unwrap());
+    assert_eq!(signal.0.bits.len(), 2);
+}
+
+#[test]
+fn test_enum_switch_and_is_eq() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let values = values();
+    let deposit: CTx<_> = Signal::alloc(cs, Some(&values[0]));
+    let transfer: CTx<_> = Signal::alloc(cs, Some(&values[1]));
+    assert!(deposit.is_deposit().get_value().unwrap());
+    assert!(!deposit.is_transfer().get_value().unwrap());
+
+    let bit = CBool::alloc(cs, Some(&false));
+    let res = deposit.switch(&bit, &transfer);
+    assert_eq!(res.get_value(), Some(values[1].clone()));
+    assert!(res.is_transfer().get_value().unwrap());
+
+    assert!(!deposit.is_eq(&transfer).get_value().unwrap());
+
+    // the fields of inactive variants are zero
+    assert_eq!(deposit.2.get_value(), Some((Num::ZERO, Num::ZERO)));
+    let mut other = deposit.clone();
+    other.2 = (CNum::alloc(cs, Some(&Num::from(1))), CNum::alloc(cs, Some(&Num::from(2))));
+    assert!(!deposit.is_eq(&other).get_value().unwrap());
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_enum_inactive_fields_constrained() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let value = Kind::Discriminant(Num::from(3));
+    let signal: CKind<_> = Signal::alloc(cs, Some(&value));
+    assert_eq!(signal.get_value(), Some(value));
+    assert!(signal.is_discriminant().get_value().unwrap());
+    assert!(cs.borrow().check().is_ok());
+
+    // a nonzero field of the inactive variant does not satisfy the circuit:
+    // the aux variables are the discriminant bits, then each field with its switch
+    cs.borrow_mut().values_aux[4] = Num::from(5);
+    assert!(cs.borrow().check().is_err());
+}
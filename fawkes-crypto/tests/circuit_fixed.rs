@@ -0,0 +1,119 @@
+use fawkes_crypto_zkbob::{
+    circuit::{cs::{CheckCS, DebugCS, CS}, fixed::CFixed, num::CNum},
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::{Num, NumRepr},
+    native::fixed::{Fixed, Rounding},
+    rand::{thread_rng, Rng},
+};
+
+type F = Fixed<Fr, 32, 16>;
+type CF = CFixed<CheckCS<Fr>, 32, 16>;
+
+#[test]
+fn test_native_fixed() {
+    let a = F::from_ratio(5, 2, Rounding::Truncate).unwrap();
+    assert_eq!(a.raw(), Num::from(5 << 15));
+    assert_eq!(a.to_int(), NumRepr::from(2));
+    assert!(a < F::from_int(3).unwrap());
+
+    // 1/3 = 0x5555.55.. / 2^16
+    assert_eq!(F::from_ratio(1, 3, Rounding::Truncate).unwrap().raw(), Num::from(0x5555));
+    // 2/3 = 0xaaaa.aa.. / 2^16
+    assert_eq!(F::from_ratio(2, 3, Rounding::Truncate).unwrap().raw(), Num::from(0xaaaa));
+    assert_eq!(F::from_ratio(2, 3, Rounding::HalfUp).unwrap().raw(), Num::from(0xaaab));
+
+    // 2^-16 * 2^-16 is rounded to 0 or 2^-16
+    let eps = F::from_raw(Num::ONE).unwrap();
+    let half = F::from_ratio(1, 2, Rounding::Truncate).unwrap();
+    assert_eq!(eps.checked_mul(&eps, Rounding::Truncate).unwrap().raw(), Num::ZERO);
+    assert_eq!(eps.checked_mul(&half, Rounding::HalfUp).unwrap().raw(), Num::ONE);
+    assert_eq!(eps.checked_mul(&half, Rounding::Truncate).unwrap().raw(), Num::ZERO);
+
+    let max = F::from_int(u32::MAX as u64).unwrap();
+    assert!(F::from_int(1 << 32).is_none());
+    assert!(max.checked_add(&F::from_int(1).unwrap()).is_none());
+    assert!(half.checked_sub(&max).is_none());
+    assert!(max.checked_mul(&max, Rounding::Truncate).is_none());
+}
+
+#[test]
+fn test_fixed_arithmetic() {
+    let mut rng = thread_rng();
+    for _ in 0..10 {
+        let a = F::from_raw(Num::from(rng.gen::<u32>() as u64)).unwrap();
+        let b = F::from_raw(Num::from(rng.gen::<u32>() as u64)).unwrap();
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let ca = CF::alloc(cs, Some(&a));
+        let cb = CF::alloc(cs, Some(&b));
+
+        assert_eq!((&ca + &cb).get_value(), a.checked_add(&b));
+        let (max, min) = if a >= b { ((&ca, a), (&cb, b)) } else { ((&cb, b), (&ca, a)) };
+        assert_eq!((max.0 - min.0).get_value(), max.1.checked_sub(&min.1));
+        for rounding in [Rounding::Truncate, Rounding::HalfUp] {
+            assert_eq!(ca.mul(&cb, rounding).get_value(), a.checked_mul(&b, rounding));
+        }
+
+        assert_eq!(ca.lt(&cb).get_value(), Some(a < b));
+        assert_eq!(ca.le(&cb).get_value(), Some(a <= b));
+        assert_eq!(ca.gt(&cb).get_value(), Some(a > b));
+        assert_eq!(ca.ge(&cb).get_value(), Some(a >= b));
+
+        assert!(cs.borrow().check().is_ok());
+    }
+}
+
+#[test]
+fn test_fixed_overflow() {
+    let max = F::from_int(u32::MAX as u64).unwrap();
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CF::alloc(cs, Some(&max));
+    let _ = &a + &a;
+    assert!(cs.borrow().check().is_err());
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CF::alloc(cs, Some(&max));
+    a.mul(&a, Rounding::Truncate);
+    assert!(cs.borrow().check().is_err());
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CF::alloc(cs, Some(&F::from_int(1).unwrap()));
+    let b = &a - &CF::alloc(cs, Some(&F::from_int(2).unwrap()));
+    assert!(b.get_value().is_none());
+    // the witness of the product of out of range values is still computed
+    b.mul(&b, Rounding::HalfUp);
+    assert!(cs.borrow().check().is_err());
+
+    // the integer part is out of range
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let n = CNum::alloc(cs, Some(&Num::from(1u64 << 32)));
+    CF::from_int(&n);
+    assert!(cs.borrow().check().is_err());
+
+    // without the integer part only zero converts
+    for (value, ok) in [(0, true), (1, false)] {
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let n = CNum::alloc(cs, Some(&Num::from(value)));
+        CFixed::<_, 0, 16>::from_int(&n);
+        assert_eq!(cs.borrow().check().is_ok(), ok, "value {}", value);
+    }
+}
+
+#[test]
+fn test_fixed_mul_constraints() {
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let a = CFixed::<_, 32, 16>::alloc(cs, Some(&F::from_ratio(3, 2, Rounding::Truncate).unwrap()));
+    let b = CFixed::<_, 32, 16>::alloc(cs, Some(&F::from_ratio(1, 3, Rounding::Truncate).unwrap()));
+
+    // range checks of the result and the dropped fractional bits and the product
+    let n = cs.borrow().num_gates();
+    a.mul(&b, Rounding::HalfUp);
+    assert_eq!(cs.borrow().num_gates() - n, 48 + 16 + 1);
+
+    let n = cs.borrow().num_gates();
+    let c: CFixed<_, 32, 16> = a.derive_const(&F::from_int(2).unwrap());
+    let d: CFixed<_, 32, 16> = a.derive_const(&F::from_int(3).unwrap());
+    assert_eq!(c.mul(&d, Rounding::Truncate).as_const(), F::from_int(6));
+    assert_eq!(cs.borrow().num_gates(), n);
+}
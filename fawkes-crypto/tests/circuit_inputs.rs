@@ -0,0 +1,86 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        bool::CBool,
+        cs::{CheckCS, CS},
+        num::CNum,
+        poseidon::CMerkleProof,
+    },
+    core::{signal::{enum_signal, Signal}, sizedvec::SizedVec},
+    engines::bn256::Fr,
+    ff_uint::{Num, PrimeField},
+    native::poseidon::MerkleProof,
+    rand::{thread_rng, Rng},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Op<Fr: PrimeField> {
+    Add(Num<Fr>, Num<Fr>),
+    Neg { a: Num<Fr> },
+    Skip,
+}
+
+#[enum_signal(binary)]
+#[derive(Clone)]
+#[Value = "Op<C::Fr>"]
+pub enum COp<C: CS> {
+    Add(CNum<C>, CNum<C>),
+    Neg { a: CNum<C> },
+    Skip,
+}
+
+// inputs as they are made by inputize
+fn inputize<S: Signal<CheckCS<Fr>>>(value: &S::Value) -> Vec<Num<Fr>> {
+    let ref cs = CheckCS::rc_new();
+    let signal = S::alloc(cs, Some(value));
+    signal.inputize();
+    let inputs = cs.borrow().values_input[1..].to_vec();
+    inputs
+}
+
+#[test]
+fn test_inputs_merkle_proof() {
+    let mut rng = thread_rng();
+    let proof = MerkleProof::<Fr, 4> {
+        sibling: (0..4).map(|_| rng.gen()).collect(),
+        path: (0..4).map(|_| rng.gen()).collect(),
+    };
+
+    type S = CMerkleProof<CheckCS<Fr>, 4>;
+    let inputs = S::value_to_inputs(&proof);
+    assert_eq!(inputs, inputize::<S>(&proof));
+
+    let res = S::value_from_inputs(&inputs).unwrap();
+    assert_eq!(res.sibling.as_slice(), proof.sibling.as_slice());
+    assert_eq!(res.path.as_slice(), proof.path.as_slice());
+
+    assert!(S::value_from_inputs(&inputs[1..]).is_none());
+    let mut wrong = inputs.clone();
+    wrong[7] = Num::from(2);
+    assert!(S::value_from_inputs(&wrong).is_none());
+}
+
+#[test]
+fn test_inputs_enum() {
+    type S = COp<CheckCS<Fr>>;
+    for value in [Op::Add(Num::from(1), Num::from(2)), Op::Neg { a: Num::from(3) }, Op::Skip].iter() {
+        let inputs = S::value_to_inputs(value);
+        assert_eq!(inputs, inputize::<S>(value));
+        assert_eq!(S::value_from_inputs(&inputs).as_ref(), Some(value));
+    }
+}
+
+#[test]
+fn test_inputs_nested() {
+    type S = (CNum<CheckCS<Fr>>, SizedVec<SizedVec<CBool<CheckCS<Fr>>, 2>, 2>);
+    let value = (
+        Num::from(5),
+        vec![vec![true, false].into_iter().collect(), vec![false, true].into_iter().collect()]
+            .into_iter()
+            .collect::<SizedVec<SizedVec<bool, 2>, 2>>(),
+    );
+    let inputs = S::value_to_inputs(&value);
+    assert_eq!(inputs, vec![Num::from(5), Num::ONE, Num::ZERO, Num::ZERO, Num::ONE]);
+    let res = S::value_from_inputs(&inputs).unwrap();
+    assert_eq!(res.0, value.0);
+    assert_eq!(res.1[1].as_slice(), value.1[1].as_slice());
+}
@@ -0,0 +1,88 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::{CheckCS, DebugCS},
+        keccak256::{c_keccak256, c_keccak_f1600},
+    },
+    core::signal::Signal,
+    engines::bn256::Fr,
+    native::{
+        bitify::{bits_be_to_bytes, bytes_to_bits_be},
+        keccak256::{keccak256, keccak_f1600},
+    },
+    rand::{thread_rng, Rng},
+};
+use sha3::{Digest, Keccak256};
+
+mod common;
+use common::{alloc_bits, bit_values, hex, num_gates};
+
+#[test]
+fn test_native_keccak256() {
+    assert_eq!(
+        hex(&keccak256(b"")),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert_eq!(
+        hex(&keccak256(b"abc")),
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+    );
+
+    let mut rng = thread_rng();
+    // the lengths around the rate of 136 bytes
+    for len in [1, 32, 135, 136, 137, 300] {
+        let data = (0..len).map(|_| rng.gen()).collect::<Vec<u8>>();
+        assert_eq!(keccak256(&data).to_vec(), Keccak256::digest(&data).to_vec(), "len {}", len);
+    }
+}
+
+#[test]
+fn test_circuit_keccak256() {
+    let mut rng = thread_rng();
+    // the longest message with the one block padding and the message, which is padded with the full block
+    for len in [4, 135, 136] {
+        let data = (0..len).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let bits = alloc_bits(cs, &bytes_to_bits_be(&data));
+        let res = bit_values(&c_keccak256(&bits));
+        assert_eq!(bits_be_to_bytes(&res), Keccak256::digest(&data).to_vec(), "len {}", len);
+        assert!(cs.borrow().check().is_ok());
+    }
+}
+
+#[test]
+fn test_circuit_keccak_f1600() {
+    let mut rng = thread_rng();
+    let mut state: [u64; 25] = rng.gen();
+    let bits = state
+        .iter()
+        .flat_map(|l| (0..64).map(move |z| (l >> z) & 1 == 1))
+        .collect::<Vec<_>>();
+    keccak_f1600(&mut state);
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let bits = alloc_bits(cs, &bits);
+    let res = c_keccak_f1600(&bits);
+    for (i, lane) in res.chunks(64).enumerate() {
+        let value = lane
+            .iter()
+            .enumerate()
+            .fold(0u64, |acc, (z, b)| acc | (b.get_value().unwrap() as u64) << z);
+        assert_eq!(value, state[i]);
+    }
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_circuit_keccak256_constraints() {
+    // per round: 3 constraints for each of 320 parities of theta, 320 xors of theta,
+    // 1600 xors of theta with the state and 2 constraints for each of 1600 bits of chi
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let state = alloc_bits(cs, &[false; 1600]);
+    let permutation = num_gates(cs, || c_keccak_f1600(&state));
+    assert_eq!(permutation, 24 * (320 * 3 + 320 + 1600 + 1600 * 2));
+
+    // the lanes of the state without the message bits are constant in the first round, so it is cheaper
+    let bits = alloc_bits(cs, &[false; 256]);
+    let one_block = num_gates(cs, || c_keccak256(&bits));
+    assert_eq!(one_block, 144256);
+}
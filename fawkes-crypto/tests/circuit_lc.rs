@@ -0,0 +1,80 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::BuildCS,
+        lc::{AbstractLC, Index, VecLC, LC},
+        num::CNum,
+        poseidon::{c_poseidon_merkle_proof_root, CMerkleProof},
+    },
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::Num,
+    native::poseidon::PoseidonParams,
+    rand::{thread_rng, Rng},
+};
+
+fn rand_lc<L: AbstractLC<Fr>, R: Rng>(rng: &mut R, terms: &[(Num<Fr>, Index)]) -> L {
+    let mut lc = L::new();
+    for &(v, i) in terms.iter() {
+        if rng.gen::<bool>() {
+            lc.add_assign(&L::from_parts(v, i));
+        } else {
+            lc.sub_assign(&L::from_parts(-v, i));
+        }
+    }
+    lc
+}
+
+#[test]
+fn test_vec_lc_matches_lc() {
+    let mut rng = thread_rng();
+    for _ in 0..100 {
+        let terms = (0..rng.gen_range(0, 20))
+            .map(|_| {
+                let i = rng.gen_range(0, 8);
+                let index = if rng.gen() { Index::Input(i) } else { Index::Aux(i) };
+                (Num::from(rng.gen_range(1u64, 4)), index)
+            })
+            .collect::<Vec<_>>();
+        let other = terms.iter().rev().map(|&(v, i)| (-v, i)).collect::<Vec<_>>();
+
+        let mut a: LC<Fr> = rand_lc(&mut rng, &terms);
+        let mut b: VecLC<Fr> = rand_lc(&mut rng, &terms);
+        assert_eq!(a.to_vec(), b.to_vec());
+
+        let k = Num::from(rng.gen_range(0u64, 3));
+        a.mul_assign(&k);
+        b.mul_assign(&k);
+        a.add_assign(&rand_lc(&mut rng, &other[..other.len() / 2]));
+        b.add_assign(&rand_lc(&mut rng, &other[..other.len() / 2]));
+        a.sub_assign(&rand_lc(&mut rng, &terms));
+        b.sub_assign(&rand_lc(&mut rng, &terms));
+        assert_eq!(a.to_vec(), b.to_vec());
+        assert_eq!(a.neg().to_vec(), b.neg().to_vec());
+        assert_eq!(a.as_const(), b.as_const());
+    }
+}
+
+#[test]
+fn test_vec_lc_build_cs() {
+    fn build<L: AbstractLC<Fr>>() -> BuildCS<Fr, L> {
+        let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+        let ref rcs = BuildCS::<Fr, L>::rc_new_with_lc();
+        let root = CNum::alloc(rcs, None);
+        root.inputize();
+        let (leaf, proof): (CNum<_>, CMerkleProof<_, 8>) = Signal::alloc(rcs, None);
+        c_poseidon_merkle_proof_root(&leaf, &proof, &poseidon_params).assert_eq(&root);
+        let cs = rcs.borrow().clone();
+        cs
+    }
+
+    let a = build::<LC<Fr>>();
+    let b = build::<VecLC<Fr>>();
+    assert_eq!(a.num_aux, b.num_aux);
+    assert_eq!(a.gates.len(), b.gates.len());
+    for (ga, gb) in a.gates.iter().zip(b.gates.iter()) {
+        assert_eq!(ga.0, gb.0);
+        assert_eq!(ga.1, gb.1);
+        assert_eq!(ga.2, gb.2);
+    }
+    assert_eq!(a.const_tracker, b.const_tracker);
+}
@@ -0,0 +1,87 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::{CheckCS, DebugCS, CS},
+        mimc::{c_mimc_feistel, c_mimc_sponge, c_pow},
+        num::CNum,
+    },
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::Num,
+    native::mimc::{mimc_feistel, mimc_sponge, pow, MimcParams},
+    rand::{thread_rng, Rng},
+};
+
+#[test]
+fn test_pow() {
+    let mut rng = thread_rng();
+    let a: Num<Fr> = rng.gen();
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let signal_a = CNum::alloc(cs, Some(&a));
+    for exp in [1, 2, 5, 7, 17] {
+        let expected = (0..exp).fold(Num::ONE, |acc, _| acc * a);
+        assert_eq!(pow(a, exp), expected);
+        assert_eq!(c_pow(&signal_a, exp).get_value(), Some(expected));
+    }
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_mimc() {
+    let mut rng = thread_rng();
+    let params = MimcParams::<Fr>::new(5, 220);
+    let (xl, xr, k): (Num<Fr>, Num<Fr>, Num<Fr>) = (rng.gen(), rng.gen(), rng.gen());
+    let inputs = (0..3).map(|_| rng.gen()).collect::<Vec<Num<Fr>>>();
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let signal_xl = CNum::alloc(cs, Some(&xl));
+    let signal_xr = CNum::alloc(cs, Some(&xr));
+    let signal_k = CNum::alloc(cs, Some(&k));
+    let (l, r) = c_mimc_feistel(&signal_xl, &signal_xr, &signal_k, &params);
+    assert_eq!((l.get_value().unwrap(), r.get_value().unwrap()), mimc_feistel(xl, xr, k, &params));
+
+    let signal_inputs = inputs.iter().map(|v| CNum::alloc(cs, Some(v))).collect::<Vec<_>>();
+    let res = c_mimc_sponge(&signal_inputs, &signal_k, &params);
+    assert_eq!(res.get_value(), Some(mimc_sponge(&inputs, k, &params)));
+    assert!(cs.borrow().check().is_ok());
+
+    // the Feistel network is a permutation for any alpha
+    let params7 = MimcParams::<Fr>::new(7, 91);
+    assert_ne!(mimc_sponge(&inputs, k, &params7), mimc_sponge(&inputs, k, &params));
+    assert_ne!(MimcParams::<Fr>::new_with_salt(5, 220, "other").c, params.c);
+}
+
+#[test]
+fn test_mimc_constraints() {
+    let mut rng = thread_rng();
+    let params = MimcParams::<Fr>::new(5, 220);
+    let inputs = (0..2).map(|_| rng.gen()).collect::<Vec<Num<Fr>>>();
+
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let signal_inputs = inputs.iter().map(|v| CNum::alloc(cs, Some(v))).collect::<Vec<_>>();
+    let k = CNum::from_const(cs, &Num::ZERO);
+
+    // 3 constraints per x^5
+    let n = cs.borrow().num_gates();
+    c_mimc_sponge(&signal_inputs, &k, &params);
+    assert_eq!(cs.borrow().num_gates() - n, 2 * 220 * 3);
+}
+
+#[test]
+fn test_mimc_known_answer() {
+    let params = MimcParams::<Fr>::new(5, 220);
+    let inputs = [Num::from(1), Num::from(2)];
+    let expected: Num<Fr> = "17953775016628717891751664329417311081910618395665343090535633768075734165770".parse().unwrap();
+    assert_eq!(mimc_sponge(&inputs, Num::ZERO, &params), expected);
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let signal_inputs = inputs.iter().map(|v| CNum::alloc(cs, Some(v))).collect::<Vec<_>>();
+    let k = CNum::from_const(cs, &Num::ZERO);
+    assert_eq!(c_mimc_sponge(&signal_inputs, &k, &params).get_value(), Some(expected));
+}
+
+#[test]
+#[should_panic(expected = "alpha should be coprime with r - 1")]
+fn test_mimc_wrong_alpha() {
+    // 3 divides r - 1 of BN254
+    MimcParams::<Fr>::new(3, 220);
+}
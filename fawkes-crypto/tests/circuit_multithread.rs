@@ -0,0 +1,108 @@
+#[cfg(feature = "multithread")]
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::{BuildCS, DebugCS, WitnessCS, CS},
+        lc::VecLC,
+        num::CNum,
+        poseidon::{c_poseidon_merkle_proof_root, CMerkleProof},
+        uint::CU64,
+    },
+    core::{signal::Signal, sizedvec::SizedVec},
+    engines::bn256::Fr,
+    native::poseidon::{poseidon_merkle_proof_root, MerkleProof, PoseidonParams},
+    rand::{thread_rng, Rng},
+};
+
+#[cfg(feature = "multithread")]
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[cfg(feature = "multithread")]
+#[test]
+fn test_signals_are_send() {
+    assert_send_sync::<CNum<WitnessCS<'static, Fr>>>();
+    assert_send_sync::<CNum<DebugCS<Fr, VecLC<Fr>>>>();
+    // the default LC is Send too, so the setup side signals could be moved across threads
+    assert_send_sync::<CNum<DebugCS<Fr>>>();
+    assert_send_sync::<CNum<BuildCS<Fr>>>();
+    // the cached bits of the uint
+    assert_send_sync::<CU64<WitnessCS<'static, Fr>>>();
+}
+
+#[cfg(feature = "multithread")]
+#[test]
+#[should_panic(expected = "cs is already borrowed")]
+fn test_reentrant_borrow_panics() {
+    let rcs = BuildCS::<Fr>::rc_new();
+    let _cs = rcs.borrow();
+    let _cs_mut = rcs.borrow_mut();
+}
+
+#[cfg(feature = "multithread")]
+#[test]
+fn test_shared_cs() {
+    let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+    let ref rcs = DebugCS::<Fr>::rc_new();
+    // the threads build parts of the same circuit, the constraint system is used by one thread at a time
+    let lock = std::sync::Mutex::new(());
+
+    let roots = std::thread::scope(|s| {
+        let handles = (0..4)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut rng = thread_rng();
+                    let leaf = rng.gen();
+                    let sibling = (0..8).map(|_| rng.gen()).collect::<SizedVec<_, 8>>();
+                    let path = (0..8).map(|_| rng.gen()).collect::<SizedVec<bool, 8>>();
+                    let proof = MerkleProof { sibling, path };
+                    let root = poseidon_merkle_proof_root(leaf, &proof, &poseidon_params);
+
+                    let _guard = lock.lock().unwrap();
+                    let signal: (CNum<_>, CMerkleProof<_, 8>) = Signal::alloc(rcs, Some(&(leaf, proof)));
+                    let res = c_poseidon_merkle_proof_root(&signal.0, &signal.1, &poseidon_params);
+                    assert_eq!(res.get_value().unwrap(), root);
+                    res
+                })
+            })
+            .collect::<Vec<_>>();
+        handles.into_iter().map(|h| h.join().unwrap()).collect::<Vec<_>>()
+    });
+
+    // all signals belong to the shared constraint system
+    assert!(roots.iter().all(|r| std::sync::Arc::ptr_eq(r.get_cs(), rcs)));
+    let num_gates = rcs.borrow().num_gates();
+    let sum = roots[1..].iter().fold(roots[0].clone(), |acc, r| acc + r);
+    sum.assert_eq(&CNum::alloc(rcs, sum.get_value().as_ref()));
+    assert_eq!(rcs.borrow().num_gates(), num_gates + 1);
+}
+
+#[cfg(feature = "multithread")]
+#[test]
+fn test_build_circuits_across_threads() {
+    let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let mut rng = thread_rng();
+                let leaf = rng.gen();
+                let sibling = (0..8).map(|_| rng.gen()).collect::<SizedVec<_, 8>>();
+                let path = (0..8).map(|_| rng.gen()).collect::<SizedVec<bool, 8>>();
+                let proof = MerkleProof { sibling, path };
+                let root = poseidon_merkle_proof_root(leaf, &proof, &poseidon_params);
+
+                let ref rcs = DebugCS::<Fr, VecLC<Fr>>::rc_new_with_lc();
+                let signal: (CNum<_>, CMerkleProof<_, 8>) = Signal::alloc(rcs, Some(&(leaf, proof)));
+
+                // signals are moved to another thread to finish the circuit there
+                let res = std::thread::spawn(move || {
+                    c_poseidon_merkle_proof_root(&signal.0, &signal.1, &PoseidonParams::new(3, 8, 53))
+                })
+                .join()
+                .unwrap();
+
+                assert_eq!(res.get_value().unwrap(), root);
+                assert!(rcs.borrow().num_gates() > 0);
+            });
+        }
+    });
+}
@@ -0,0 +1,141 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::{CheckCS, DebugCS, CS},
+        nonnative::CNonNative,
+        num::CNum,
+    },
+    core::signal::Signal,
+    engines::bn256::{Fq, Fr, Fs},
+    ff_uint::{Num, PrimeField},
+    rand::{thread_rng, Rng},
+};
+
+fn check_arithmetic<F: PrimeField>() {
+    let mut rng = thread_rng();
+    let (a, b): (Num<F>, Num<F>) = (rng.gen(), rng.gen());
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let ca = CNonNative::alloc(cs, Some(&a));
+    let cb = CNonNative::alloc(cs, Some(&b));
+
+    assert_eq!((&ca + &cb).get_value(), Some(a + b));
+    assert_eq!((&ca - &cb).get_value(), Some(a - b));
+    assert_eq!((-&ca).get_value(), Some(-a));
+    assert_eq!((&ca * &cb).get_value(), Some(a * b));
+    assert_eq!(ca.inv().get_value(), Some(a.checked_inv().unwrap()));
+
+    // lazy additions and subtractions before the multiplication
+    let mut acc = ca.clone();
+    let mut value = a;
+    for _ in 0..100 {
+        acc = &(&acc + &cb) - &ca;
+        value = value + b - a;
+    }
+    assert_eq!(acc.get_value(), Some(value));
+    assert_eq!((&acc * &acc).get_value(), Some(value * value));
+
+    (&ca * &cb).assert_const(&(a * b));
+    (&(&ca + &cb) * &ca).assert_eq(&(&(&ca * &ca) + &(&ca * &cb)));
+    assert_eq!(ca.is_eq(&cb).get_value(), Some(false));
+    assert_eq!((&ca - &cb).is_eq(&(&ca + &(-&cb))).get_value(), Some(true));
+
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_nonnative_fs() {
+    check_arithmetic::<Fs>();
+}
+
+#[test]
+fn test_nonnative_fq() {
+    check_arithmetic::<Fq>();
+}
+
+#[test]
+fn test_nonnative_assert_eq_fails() {
+    let mut rng = thread_rng();
+    let a: Num<Fq> = rng.gen();
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let ca = CNonNative::alloc(cs, Some(&a));
+    (&ca + &ca).assert_const(&(a + Num::ONE));
+    assert!(cs.borrow().check().is_err());
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let zero = CNonNative::<_, Fq>::alloc(cs, Some(&Num::ZERO));
+    zero.inv();
+    assert!(cs.borrow().check().is_err());
+}
+
+#[test]
+fn test_nonnative_from_num() {
+    let mut rng = thread_rng();
+    let a: Num<Fr> = rng.gen();
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let ca = CNum::alloc(cs, Some(&a));
+    // Fr is larger than Fs, so the value is reduced
+    let fs = CNonNative::<_, Fs>::from_num(&ca);
+    assert_eq!(fs.get_value(), Some(a.to_other_reduced()));
+    let fq = CNonNative::<_, Fq>::from_num(&ca);
+    assert_eq!(fq.get_value(), Some(a.to_other().unwrap()));
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_nonnative_inputs() {
+    let mut rng = thread_rng();
+    let a: Num<Fq> = rng.gen();
+    let inputs = CNonNative::<CheckCS<Fr>, Fq>::value_to_inputs(&a);
+    assert_eq!(inputs.len(), 4);
+    assert_eq!(CNonNative::<CheckCS<Fr>, Fq>::value_from_inputs(&inputs), Some(a));
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let ca = CNonNative::alloc(cs, Some(&a));
+    (&ca + &ca).inputize();
+    assert_eq!(
+        CNonNative::<CheckCS<Fr>, Fq>::value_from_inputs(&cs.borrow().values_input[1..]),
+        Some(a + a)
+    );
+}
+
+fn report_constraints<F: PrimeField>(name: &str) {
+    let mut rng = thread_rng();
+    let (a, b): (Num<F>, Num<F>) = (rng.gen(), rng.gen());
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let ca = CNonNative::alloc(cs, Some(&a));
+    let cb = CNonNative::alloc(cs, Some(&b));
+
+    let n = cs.borrow().num_gates();
+    let _ = &ca + &cb;
+    let _ = &ca - &cb;
+    assert_eq!(cs.borrow().num_gates(), n, "addition and subtraction are free");
+
+    let n = cs.borrow().num_gates();
+    let _ = &ca * &cb;
+    let mul = cs.borrow().num_gates() - n;
+
+    let n = cs.borrow().num_gates();
+    ca.inv();
+    let inv = cs.borrow().num_gates() - n;
+
+    let n = cs.borrow().num_gates();
+    ca.assert_eq(&ca);
+    let assert_eq = cs.borrow().num_gates() - n;
+
+    let n = cs.borrow().num_gates();
+    ca.is_eq(&cb);
+    let is_eq = cs.borrow().num_gates() - n;
+
+    println!(
+        "{} over bn256 Fr constraints: mul = {}, inv = {}, assert_eq = {}, is_eq = {}",
+        name, mul, inv, assert_eq, is_eq
+    );
+}
+
+#[test]
+fn test_nonnative_constraints() {
+    report_constraints::<Fs>("Fs");
+    report_constraints::<Fq>("Fq");
+}
@@ -0,0 +1,94 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::{BuildCS, CheckCS, CS, RCS},
+        gates::check_gates,
+        num::CNum,
+        optimizer::{map_aux, optimize},
+        poseidon::{c_poseidon_merkle_proof_root, CMerkleProof},
+    },
+    core::{signal::Signal, sizedvec::SizedVec},
+    engines::bn256::Fr,
+    ff_uint::Num,
+    native::poseidon::{poseidon_merkle_proof_root, MerkleProof, PoseidonParams},
+    rand::{thread_rng, Rng},
+};
+
+fn merkle_circuit<C: CS>(public: CNum<C>, secret: (CNum<C>, CMerkleProof<C, 8>)) {
+    let poseidon_params = PoseidonParams::<C::Fr>::new(3, 8, 53);
+    let res = c_poseidon_merkle_proof_root(&secret.0, &secret.1, &poseidon_params);
+    res.assert_eq(&public);
+}
+
+fn build<Pub: Signal<BuildCS<Fr>>, Sec: Signal<BuildCS<Fr>>, C: Fn(Pub, Sec)>(circuit: C) -> BuildCS<Fr> {
+    let ref rcs = BuildCS::rc_new();
+    let signal_pub = Pub::alloc(rcs, None);
+    signal_pub.inputize();
+    circuit(signal_pub, Sec::alloc(rcs, None));
+    let cs = rcs.borrow().clone();
+    cs
+}
+
+fn witness<Pub: Signal<CheckCS<Fr>>, Sec: Signal<CheckCS<Fr>>, C: Fn(Pub, Sec)>(
+    circuit: C,
+    input_pub: &Pub::Value,
+    input_sec: &Sec::Value,
+) -> RCS<CheckCS<Fr>> {
+    let rcs = CheckCS::rc_new();
+    let signal_pub = Pub::alloc(&rcs, Some(input_pub));
+    signal_pub.inputize();
+    circuit(signal_pub, Sec::alloc(&rcs, Some(input_sec)));
+    rcs
+}
+
+#[test]
+fn test_optimizer_merkle_proof() {
+    let cs = build(merkle_circuit);
+    let (opt, aux_map) = optimize(&cs);
+    assert!(opt.gates.len() < cs.gates.len());
+    assert!(opt.num_aux < cs.num_aux);
+    assert_eq!(opt.num_aux, aux_map.len());
+    assert_eq!(opt.num_input, cs.num_input);
+
+    let mut rng = thread_rng();
+    let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+    let leaf = rng.gen();
+    let sibling = (0..8).map(|_| rng.gen()).collect::<SizedVec<_, 8>>();
+    let path = (0..8).map(|_| rng.gen()).collect::<SizedVec<bool, 8>>();
+    let proof = MerkleProof { sibling, path };
+    let root = poseidon_merkle_proof_root(leaf, &proof, &poseidon_params);
+
+    let rcs = witness(merkle_circuit, &root, &(leaf, proof.clone()));
+    let w = rcs.borrow();
+    let values_aux = map_aux(&w.values_aux, &aux_map);
+    assert!(check_gates(opt.gates.iter(), &w.values_input, &values_aux).is_ok());
+
+    // the optimized gates are still not satisfied by the wrong witness
+    let rcs = witness(merkle_circuit, &(root + Num::ONE), &(leaf, proof));
+    let w = rcs.borrow();
+    let values_aux = map_aux(&w.values_aux, &aux_map);
+    assert!(check_gates(opt.gates.iter(), &w.values_input, &values_aux).is_err());
+}
+
+#[test]
+fn test_optimizer_linear_and_duplicated() {
+    fn circuit<C: CS>(public: CNum<C>, secret: (CNum<C>, CNum<C>)) {
+        secret.0.assert_eq(&secret.1);
+        (&secret.0 * &secret.1).assert_eq(&public);
+        (&secret.1 * &secret.1).assert_eq(&public);
+    }
+
+    let cs = build(circuit);
+    assert_eq!(cs.gates.len(), 6);
+    assert_eq!(cs.num_aux, 5);
+
+    let (opt, aux_map) = optimize(&cs);
+    // public aux and both products are substituted with the public input, secret.0 is substituted with secret.1,
+    // so both multiplications become the same gate secret.1 * secret.1 = public
+    assert_eq!(opt.gates.len(), 1);
+    assert_eq!(aux_map, vec![2]);
+
+    let a = Num::from(7);
+    let rcs = witness(circuit, &(a * a), &(a, a));
+    let w = rcs.borrow();
+    assert!(check_gates(opt.gates.iter(), &w.values_input, &map_aux(&w.values_aux, &aux_map)).is_ok());
+}
@@ -0,0 +1,77 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::{CheckCS, DebugCS},
+        pedersen::c_pedersen_hash,
+    },
+    core::signal::Signal,
+    engines::bn256::{Fr, Fs, JubJubBN256},
+    ff_uint::Num,
+    native::pedersen::{pedersen_hash, PedersenParams, SEGMENT_BITS},
+    rand::{thread_rng, Rng},
+};
+
+mod common;
+use common::{alloc_bits, num_gates};
+
+#[test]
+fn test_native_pedersen_hash() {
+    let jubjub_params = JubJubBN256::new();
+    let params = PedersenParams::new("test", 2, &jubjub_params);
+    let g = params.generators[0];
+    assert!(g.into_extended().is_in_subgroup(&jubjub_params));
+
+    // the windows 1 + 1 + 4 = 6 and -(1 + 2) = -3
+    let bits = [true, false, true, false, false, true, false, true];
+    let expected = g.mul(Num::<Fs>::from(6) - Num::from(3 * 32), &jubjub_params);
+    assert_eq!(pedersen_hash(&bits, &params, &jubjub_params), expected);
+    // the last window is padded with zeros
+    assert_eq!(
+        pedersen_hash(&bits[..6], &params, &jubjub_params),
+        g.mul(Num::from(6 + 3 * 32), &jubjub_params)
+    );
+
+    // the second segment uses the next generator
+    let mut bits = vec![false; SEGMENT_BITS + 1];
+    bits[SEGMENT_BITS] = true;
+    // all the windows of the first segment are 1
+    let first = (0..50).fold(Num::<Fs>::ZERO, |acc, _| acc * Num::from(32) + Num::ONE);
+    let expected = g
+        .mul(first, &jubjub_params)
+        .add(&params.generators[1].mul(Num::from(2), &jubjub_params), &jubjub_params);
+    assert_eq!(pedersen_hash(&bits, &params, &jubjub_params), expected);
+
+    let other = PedersenParams::new("other", 1, &jubjub_params);
+    assert_ne!(other.generators[0], g);
+}
+
+#[test]
+fn test_circuit_pedersen_hash() {
+    let mut rng = thread_rng();
+    let jubjub_params = JubJubBN256::new();
+    let params = PedersenParams::new("test", 3, &jubjub_params);
+
+    // the lengths around the window and the segment boundaries
+    for len in [1, 4, 254, SEGMENT_BITS, SEGMENT_BITS + 3, 3 * SEGMENT_BITS] {
+        let bits = (0..len).map(|_| rng.gen()).collect::<Vec<bool>>();
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let signal_bits = alloc_bits(cs, &bits);
+        let res = c_pedersen_hash(&signal_bits, &params, &jubjub_params);
+        assert_eq!(
+            res.get_value(),
+            Some(pedersen_hash(&bits, &params, &jubjub_params)),
+            "len {}",
+            len
+        );
+        assert!(cs.borrow().check().is_ok());
+    }
+}
+
+#[test]
+fn test_circuit_pedersen_hash_constraints() {
+    let jubjub_params = JubJubBN256::new();
+    let params = PedersenParams::new("test", 2, &jubjub_params);
+
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let bits = alloc_bits(cs, &[true; 254]);
+    assert_eq!(num_gates(cs, || c_pedersen_hash(&bits, &params, &jubjub_params)), 455);
+}
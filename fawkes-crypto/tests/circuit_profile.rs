@@ -0,0 +1,68 @@
+use fawkes_crypto_zkbob::{
+    circuit::{cs::{DebugCS, CS}, num::CNum, poseidon::*, scope::{Scope, ScopeNames}},
+    core::{signal::Signal, sizedvec::SizedVec},
+    engines::bn256::Fr,
+    native::poseidon::{MerkleProof, PoseidonParams},
+    rand::{thread_rng, Rng},
+};
+
+#[test]
+fn test_profile_poseidon_merkle_root() {
+    const PROOF_LENGTH: usize = 8;
+
+    let mut rng = thread_rng();
+    let poseidon_params = PoseidonParams::<Fr>::new(3, 8, 53);
+
+    let ref mut cs = DebugCS::rc_new();
+
+    let leaf = rng.gen();
+    let sibling = (0..PROOF_LENGTH)
+        .map(|_| rng.gen())
+        .collect::<SizedVec<_, PROOF_LENGTH>>();
+    let path = (0..PROOF_LENGTH)
+        .map(|_| rng.gen())
+        .collect::<SizedVec<bool, PROOF_LENGTH>>();
+
+    let signal_leaf = CNum::alloc(cs, Some(&leaf));
+    let signal_proof = CMerkleProof::alloc(cs, Some(&MerkleProof { sibling, path }));
+
+    {
+        let _scope = Scope::new(cs, "merkle");
+        c_poseidon_merkle_proof_root(&signal_leaf, &signal_proof, &poseidon_params);
+    }
+
+    let profile = cs.borrow().profiler.report();
+    println!("{}", profile);
+
+    assert_eq!(profile.num_gates, cs.borrow().num_gates());
+    assert_eq!(profile.num_aux, cs.borrow().num_aux());
+
+    let root = profile.get("merkle/poseidon_merkle_proof_root").unwrap();
+    let poseidon = root.get("poseidon").unwrap();
+    assert_eq!(poseidon.num_calls, PROOF_LENGTH);
+    // each level is a poseidon hash and one switch
+    assert_eq!(root.num_gates, poseidon.num_gates + PROOF_LENGTH);
+    assert!(poseidon.lc_density >= 3 * poseidon.num_gates);
+}
+
+#[test]
+fn test_scope_names() {
+    let mut scopes = ScopeNames::new();
+    scopes.push("a");
+    scopes.push("b");
+    let ab = scopes.current();
+    scopes.pop();
+    scopes.pop();
+    scopes.push("b");
+    let b = scopes.current();
+    scopes.pop();
+    scopes.push("a");
+    scopes.push("b");
+
+    // the same path gets the same id
+    assert_eq!(scopes.current(), ab);
+    assert_ne!(b, ab);
+    assert_eq!(scopes.get(ab), "a/b");
+    assert_eq!(scopes.get(b), "b");
+    assert_eq!(scopes.paths.len(), 4);
+}
@@ -0,0 +1,52 @@
+use fawkes_crypto_zkbob::{
+    backend::bellman_groth16::verifier::public_inputs,
+    circuit::{
+        bool::CBool,
+        cs::{BuildCS, CheckCS, CS, RCS},
+        num::CNum,
+    },
+    core::signal::{PublicSignal, Signal},
+    engines::bn256::Fr,
+    ff_uint::Num,
+};
+
+#[derive(Clone, Signal)]
+#[GenerateValue = "Transfer"]
+pub struct CTransfer<C: CS> {
+    #[public = "1"]
+    pub root: CNum<C>,
+    pub secret: CNum<C>,
+    #[public = "0"]
+    pub nullifier: CNum<C>,
+}
+
+#[derive(Clone, Signal)]
+#[GenerateValue = "Flags"]
+pub struct CFlags<C: CS>(#[public] CNum<C>, CBool<C>, #[public] CBool<C>);
+
+#[test]
+fn test_public_order() {
+    let value = Transfer::<Fr> { root: Num::from(1), secret: Num::from(2), nullifier: Num::from(3) };
+    let public = CTransfer::<CheckCS<Fr>>::public_value(&value);
+    assert_eq!(public, (Num::from(3), Num::from(1)));
+    // the verifier side knows only the public part
+    assert_eq!(public_inputs::<CheckCS<Fr>, CTransfer<_>>(&public), vec![Num::from(3), Num::from(1)]);
+
+    let ref rcs = BuildCS::<Fr>::rc_new();
+    let signal = CTransfer::alloc(rcs, None);
+    signal.inputize_public();
+    assert_eq!(rcs.borrow().num_input, 3);
+}
+
+#[test]
+fn test_public_declaration_order() {
+    let value = Flags::<Fr>(Num::from(5), false, true);
+    let public = CFlags::<CheckCS<Fr>>::public_value(&value);
+    assert_eq!(public_inputs::<CheckCS<Fr>, CFlags<_>>(&public), vec![Num::from(5), Num::ONE]);
+
+    let ref rcs = CheckCS::<Fr>::rc_new();
+    let signal = CFlags::alloc(rcs, Some(&value));
+    signal.inputize_public();
+    assert!(rcs.borrow().check().is_ok());
+    assert_eq!(rcs.borrow().values_input[1..], public_inputs::<BuildCS<Fr>, CFlags<_>>(&(Num::from(5), true))[..]);
+}
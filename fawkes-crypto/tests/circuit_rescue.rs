@@ -0,0 +1,70 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        cs::{CheckCS, DebugCS, CS},
+        num::CNum,
+        rescue::{c_rescue_perm, c_rescue_sponge},
+    },
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::Num,
+    native::{
+        mimc::pow,
+        rescue::{perm, pow_inv, rescue_sponge, RescueParams},
+    },
+    rand::{thread_rng, Rng},
+};
+
+#[test]
+fn test_rescue() {
+    let mut rng = thread_rng();
+    let params = RescueParams::<Fr>::new(3, 8, 5);
+    let a: Num<Fr> = rng.gen();
+    assert_eq!(pow(pow_inv(a, &params), 5), a);
+
+    let mut state = (0..3).map(|_| rng.gen()).collect::<Vec<Num<Fr>>>();
+    let inputs = (0..5).map(|_| rng.gen()).collect::<Vec<Num<Fr>>>();
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let signal_state = state.iter().map(|v| CNum::alloc(cs, Some(v))).collect::<Vec<_>>();
+    let res = c_rescue_perm(&signal_state, &params);
+    perm(&mut state, &params);
+    assert_eq!(res.iter().map(|v| v.get_value().unwrap()).collect::<Vec<_>>(), state);
+
+    let signal_inputs = inputs.iter().map(|v| CNum::alloc(cs, Some(v))).collect::<Vec<_>>();
+    let res = c_rescue_sponge(&signal_inputs, &params);
+    assert_eq!(res.get_value(), Some(rescue_sponge(&inputs, &params)));
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_rescue_constraints() {
+    let mut rng = thread_rng();
+    let params = RescueParams::<Fr>::new(3, 8, 5);
+    let state = (0..3).map(|_| rng.gen()).collect::<Vec<Num<Fr>>>();
+
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let signal_state = state.iter().map(|v| CNum::alloc(cs, Some(v))).collect::<Vec<_>>();
+
+    // 3 constraints per x^5 and per x^(1/5)
+    let n = cs.borrow().num_gates();
+    c_rescue_perm(&signal_state, &params);
+    assert_eq!(cs.borrow().num_gates() - n, 8 * 2 * 3 * 3);
+}
+
+#[test]
+fn test_rescue_known_answer() {
+    let params = RescueParams::<Fr>::new(3, 8, 5);
+    let inputs = [Num::from(1), Num::from(2)];
+    let expected: Num<Fr> = "16438838013225948843897706784361270215735667370431442203219152832210730026105".parse().unwrap();
+    assert_eq!(rescue_sponge(&inputs, &params), expected);
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let signal_inputs = inputs.iter().map(|v| CNum::alloc(cs, Some(v))).collect::<Vec<_>>();
+    assert_eq!(c_rescue_sponge(&signal_inputs, &params).get_value(), Some(expected));
+}
+
+#[test]
+#[should_panic(expected = "should be at least one input")]
+fn test_rescue_empty_input() {
+    rescue_sponge::<Fr>(&[], &RescueParams::new(3, 8, 5));
+}
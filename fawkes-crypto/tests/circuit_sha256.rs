@@ -0,0 +1,117 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        bitify::{c_bits_be_to_bytes, c_bytes_to_bits_be},
+        cs::{CheckCS, DebugCS},
+        num::CNum,
+        sha256::{c_sha256, c_sha256_bytes, c_sha256_compress},
+    },
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::Num,
+    native::{
+        bitify::{bits_be_to_bytes, bytes_to_bits_be},
+        sha256::{sha256, sha256_compress, IV},
+    },
+    rand::{thread_rng, Rng},
+};
+
+mod common;
+use common::{alloc_bits, bit_values, hex, num_gates};
+
+#[test]
+fn test_native_sha256() {
+    assert_eq!(
+        hex(&sha256(b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        hex(&sha256(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex(&sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+}
+
+#[test]
+fn test_circuit_sha256() {
+    let mut rng = thread_rng();
+    // the lengths around the block and the padding boundaries
+    for len in [1, 32, 55, 56, 64, 100] {
+        let data = (0..len).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let ref cs = CheckCS::<Fr>::rc_new();
+        let bits = alloc_bits(cs, &bytes_to_bits_be(&data));
+        let res = bit_values(&c_sha256(&bits));
+        assert_eq!(bits_be_to_bytes(&res), sha256(&data).to_vec(), "len {}", len);
+        assert!(cs.borrow().check().is_ok());
+    }
+}
+
+#[test]
+fn test_circuit_sha256_bytes() {
+    let data = b"abc";
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let bytes = data
+        .iter()
+        .map(|b| CNum::alloc(cs, Some(&Num::from(*b as u64))))
+        .collect::<Vec<_>>();
+    let res = c_sha256_bytes(&bytes);
+    let expected = sha256(data);
+    assert_eq!(res.len(), expected.len());
+    for (r, e) in res.iter().zip(expected.iter()) {
+        assert_eq!(r.get_value().unwrap(), Num::from(*e as u64));
+    }
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_circuit_sha256_compress() {
+    let mut rng = thread_rng();
+    let block = (0..64).map(|_| rng.gen()).collect::<Vec<u8>>();
+    let mut state = IV;
+    sha256_compress(&mut state, &block);
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let state_bits = IV.iter().flat_map(|v| v.to_be_bytes()).collect::<Vec<_>>();
+    let state_bits = alloc_bits(cs, &bytes_to_bits_be(&state_bits));
+    let block_bits = alloc_bits(cs, &bytes_to_bits_be(&block));
+    let res = bit_values(&c_sha256_compress(&state_bits, &block_bits));
+    let expected = state.iter().flat_map(|v| v.to_be_bytes()).collect::<Vec<_>>();
+    assert_eq!(bits_be_to_bytes(&res), expected);
+}
+
+#[test]
+fn test_bytes_to_bits() {
+    let data = [0x80u8, 0x01, 0xa5];
+    assert_eq!(bits_be_to_bytes(&bytes_to_bits_be(&data)), data.to_vec());
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let bytes = data
+        .iter()
+        .map(|b| CNum::alloc(cs, Some(&Num::from(*b as u64))))
+        .collect::<Vec<_>>();
+    let bits = c_bytes_to_bits_be(&bytes);
+    assert_eq!(
+        bits.iter().map(|b| b.get_value().unwrap()).collect::<Vec<_>>(),
+        bytes_to_bits_be(&data)
+    );
+    let res = c_bits_be_to_bytes(&bits);
+    for (r, b) in res.iter().zip(data.iter()) {
+        assert_eq!(r.get_value().unwrap(), Num::from(*b as u64));
+    }
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_circuit_sha256_constraints() {
+    let ref cs = DebugCS::<Fr>::rc_new();
+    let bits = alloc_bits(cs, &[false; 512]);
+    let state = alloc_bits(cs, &[false; 256]);
+
+    let compress = num_gates(cs, || c_sha256_compress(&state, &bits));
+    // the initial state and the padding block are constant
+    let one_block = num_gates(cs, || c_sha256(&bits[..256]));
+    assert_eq!(compress, 26416);
+    assert_eq!(one_block, 25468);
+}
@@ -0,0 +1,131 @@
+use fawkes_crypto_zkbob::{
+    circuit::{cs::{BuildCS, CheckCS}, num::CNum, uint::{CUint, CU64}},
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::{Num, NumRepr, Uint},
+    rand::{thread_rng, Rng},
+};
+
+type U64 = CUint<CheckCS<Fr>, 64>;
+
+fn repr(v: u64) -> NumRepr<<Fr as fawkes_crypto_zkbob::ff_uint::PrimeFieldParams>::Inner> {
+    NumRepr::from(v)
+}
+
+fn value(u: &U64) -> u64 {
+    u.get_value().unwrap().0.as_u64()
+}
+
+#[test]
+fn test_uint_arithmetic() {
+    let mut rng = thread_rng();
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let (a, b): (u64, u64) = (rng.gen::<u32>() as u64, rng.gen::<u32>() as u64);
+    let (ca, cb) = (U64::alloc(cs, Some(&repr(a))), U64::alloc(cs, Some(&repr(b))));
+
+    assert_eq!(value(&(&ca + &cb)), a + b);
+    assert_eq!(value(&(&ca * &cb)), a * b);
+    let (max, min) = if a >= b { (&ca, &cb) } else { (&cb, &ca) };
+    assert_eq!(value(&(max - min)), a.max(b) - a.min(b));
+
+    assert_eq!(value(&(&ca & &cb)), a & b);
+    assert_eq!(value(&(&ca | &cb)), a | b);
+    assert_eq!(value(&(&ca ^ &cb)), a ^ b);
+    assert_eq!(value(&(&ca << 40)), a << 40);
+    assert_eq!(value(&(&ca >> 7)), a >> 7);
+
+    assert_eq!(ca.lt(&cb).get_value().unwrap(), a < b);
+    assert_eq!(ca.le(&cb).get_value().unwrap(), a <= b);
+    assert_eq!(ca.gt(&cb).get_value().unwrap(), a > b);
+    assert_eq!(ca.ge(&cb).get_value().unwrap(), a >= b);
+    assert!(ca.le(&ca).get_value().unwrap());
+    assert!(!ca.lt(&ca).get_value().unwrap());
+
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_uint_overflow() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = U64::alloc(cs, Some(&repr(u64::MAX)));
+    let _ = &a + &a;
+    assert!(cs.borrow().check().is_err());
+
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = U64::alloc(cs, Some(&repr(1)));
+    let b = U64::alloc(cs, Some(&repr(2)));
+    let _ = &a - &b;
+    assert!(cs.borrow().check().is_err());
+
+    // the value does not fit into 64 bits
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let n = CNum::alloc(cs, Some(&Num::from(u64::MAX as u128 + 1)));
+    U64::from_num(&n);
+    assert!(cs.borrow().check().is_err());
+}
+
+#[test]
+fn test_uint_lazy_bits() {
+    let ref cs = BuildCS::<Fr>::rc_new();
+    let a = CUint::<_, 64>::alloc(cs, None);
+    let b = CUint::<_, 64>::alloc(cs, None);
+    let gates = cs.borrow().gates.len();
+
+    // the bits of the allocated values are cached
+    let _ = &a & &b;
+    assert_eq!(cs.borrow().gates.len(), gates + 64);
+
+    // the bits of switch result are computed only once
+    let c = a.switch(&a.bits()[0], &b);
+    let gates = cs.borrow().gates.len();
+    c.bits();
+    c.bits();
+    assert_eq!(cs.borrow().gates.len(), gates + 64);
+}
+
+#[test]
+fn test_uint_inputs() {
+    let value = repr(u64::MAX);
+    let inputs = U64::value_to_inputs(&value);
+    assert_eq!(U64::value_from_inputs(&inputs), Some(value));
+    assert_eq!(U64::value_from_inputs(&[Num::from(u64::MAX as u128 + 1)]), None);
+}
+
+#[test]
+fn test_uint_native() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CU64::alloc_native(cs, Some(u64::MAX));
+    assert_eq!(a.get_value_native::<u64>(), Some(u64::MAX));
+    assert_eq!(a.get_value_native::<u32>(), None);
+
+    // 128 bits are too many for mul, but enough for add
+    let b = CUint::<_, 128>::from_const_native(cs, u128::MAX);
+    assert_eq!(b.as_const_native::<u128>(), Some(u128::MAX));
+    assert_eq!(b.get_value_native::<u64>(), None);
+
+    let c = CUint::<_, 128>::alloc_native(cs, Some(u64::MAX));
+    assert_eq!(c.get_value(), Some(repr(u64::MAX)));
+    assert_eq!((&c + &c).get_value_native::<u128>(), Some(2 * u64::MAX as u128));
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+fn test_uint_widest() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let max = (1u128 << 126) - 1;
+    let a = CUint::<_, 126>::alloc_native(cs, Some(max));
+    let b = CUint::<_, 126>::alloc_native(cs, Some(3u8));
+    let (q, r) = a.div_rem(&b);
+    assert_eq!(q.get_value_native::<u128>(), Some(max / 3));
+    assert_eq!(r.get_value_native::<u128>(), Some(max % 3));
+    assert_eq!((&q * &b).get_value_native::<u128>(), Some(max - max % 3));
+    assert!(cs.borrow().check().is_ok());
+}
+
+#[test]
+#[should_panic(expected = "uint is too large for the field")]
+fn test_uint_mul_too_wide() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let a = CUint::<_, 127>::alloc_native(cs, Some(1u8));
+    let _ = &a * &a;
+}
@@ -0,0 +1,94 @@
+use fawkes_crypto_zkbob::{
+    circuit::{
+        bool::CBool,
+        cs::{CheckCS, CS, RCS},
+        ecc::CEdwardsPoint,
+        num::CNum,
+    },
+    core::{signal::Signal, sizedvec::SizedVec},
+    engines::bn256::{Fr, JubJubBN256},
+    ff_uint::Num,
+    native::ecc::{EdwardsPoint, JubJubParams},
+    BorshDeserialize, BorshSerialize,
+};
+
+#[derive(Clone, Signal)]
+#[GenerateValue = "Note"]
+pub struct CNote<C: CS, const L: usize> {
+    pub amount: CNum<C>,
+    pub flags: SizedVec<CBool<C>, L>,
+    pub pair: (CNum<C>, CBool<C>),
+    #[Value = "EdwardsPoint<Fr>"]
+    pub owner: CEdwardsPoint<C>,
+}
+
+#[derive(Clone, Signal)]
+#[GenerateValue = "Pair"]
+pub struct CPair<C: CS>(CNum<C>, CBool<C>);
+
+// the dependency could be renamed, then the path to the crate is set explicitly
+mod renamed {
+    pub use fawkes_crypto_zkbob::*;
+}
+
+#[derive(Clone, Signal)]
+#[GenerateValue = "RenamedPair"]
+#[signal(crate = "crate::renamed")]
+pub struct CRenamedPair<C: CS> {
+    #[public]
+    pub a: CNum<C>,
+    pub b: CBool<C>,
+}
+
+#[test]
+fn test_generated_value() {
+    let params = JubJubBN256::new();
+    let value = Note::<Fr, 2> {
+        amount: Num::from(10),
+        flags: vec![true, false].into_iter().collect(),
+        pair: (Num::from(3), true),
+        owner: params.edwards_g().clone(),
+    };
+
+    let ref cs = CheckCS::rc_new();
+    let signal = CNote::<_, 2>::alloc(cs, Some(&value));
+    let res = signal.get_value().unwrap();
+    assert_eq!(res.amount, value.amount);
+    assert_eq!(res.flags.as_slice(), value.flags.as_slice());
+    assert_eq!(res.pair, value.pair);
+    assert_eq!(res.owner, value.owner);
+}
+
+#[test]
+fn test_generated_tuple_value() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let value = Pair(Num::from(5), false);
+    let signal = CPair::from_const(cs, &value);
+    let res = signal.as_const().unwrap();
+    assert_eq!(res.0, value.0);
+    assert_eq!(res.1, value.1);
+
+    let data = value.try_to_vec().unwrap();
+    let res = Pair::<Fr>::try_from_slice(&data).unwrap();
+    assert_eq!(res.0, value.0);
+    assert_eq!(res.1, value.1);
+
+    let data = serde_json::to_string(&value).unwrap();
+    let res: Pair<Fr> = serde_json::from_str(&data).unwrap();
+    assert_eq!(res.0, value.0);
+    assert_eq!(res.1, value.1);
+}
+
+#[test]
+fn test_renamed_crate() {
+    let ref cs = CheckCS::<Fr>::rc_new();
+    let value = RenamedPair { a: Num::from(7), b: true };
+    let signal = CRenamedPair::alloc(cs, Some(&value));
+    let res = signal.get_value().unwrap();
+    assert_eq!(res.a, value.a);
+    assert_eq!(res.b, value.b);
+
+    let data = serde_json::to_string(&value).unwrap();
+    let res: RenamedPair<Fr> = serde_json::from_str(&data).unwrap();
+    assert_eq!(res.a, value.a);
+}
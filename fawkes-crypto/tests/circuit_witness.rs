@@ -0,0 +1,54 @@
+use bit_vec::BitVec;
+use fawkes_crypto_zkbob::{
+    borsh::{BorshDeserialize, BorshSerialize},
+    circuit::{circom::split_witness, cs::WitnessCS, gates::GateSource, num::CNum, witness::Witness},
+    core::signal::Signal,
+    engines::bn256::Fr,
+    ff_uint::Num,
+};
+
+fn build() -> Witness<Fr> {
+    let gates = vec![];
+    let const_tracker = BitVec::new();
+    let ref rcs = WitnessCS::rc_new(0, GateSource::Precomputed(&gates), &const_tracker);
+    let a = Num::from(3);
+    let b = Num::from(5);
+    let public = CNum::alloc(rcs, Some(&(a + b)));
+    public.inputize();
+    let secret: (CNum<_>, CNum<_>) = Signal::alloc(rcs, Some(&(a, b)));
+    (&secret.0 + &secret.1).assert_eq(&public);
+    let cs = rcs.borrow();
+    Witness::from_cs(&cs)
+}
+
+#[test]
+fn test_witness_borsh() {
+    let witness = build();
+    assert_eq!(witness.inputs(), &[Num::from(8)]);
+
+    let data = witness.try_to_vec().unwrap();
+    let res = Witness::<Fr>::try_from_slice(&data).unwrap();
+    assert_eq!(res, witness);
+}
+
+#[test]
+fn test_witness_wtns() {
+    let witness = build();
+    let mut data = vec![];
+    witness.write_wtns(&mut data).unwrap();
+
+    let res = Witness::<Fr>::read_wtns(&mut &data[..], witness.values_input.len()).unwrap();
+    assert_eq!(res, witness);
+}
+
+#[test]
+fn test_witness_wrong_length() {
+    let mut data = vec![];
+    Witness::<Fr> { values_input: vec![], values_aux: vec![] }.write_wtns(&mut data).unwrap();
+    assert!(Witness::<Fr>::read_wtns(&mut &data[..], 0).is_err());
+    assert!(Witness::<Fr>::read_wtns(&mut &data[..], 1).is_err());
+
+    assert!(split_witness::<Fr>(&[], 0, 0).is_err());
+    assert!(split_witness::<Fr>(&[Num::ONE], 1, 1).is_err());
+    assert!(split_witness::<Fr>(&[Num::ONE, Num::ZERO], 1, 1).is_ok());
+}
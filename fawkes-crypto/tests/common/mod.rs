@@ -0,0 +1,29 @@
+// Helpers shared by the tests of the hash gadgets, every test crate uses only a part of them
+#![allow(dead_code)]
+
+use fawkes_crypto_zkbob::{
+    circuit::{
+        bool::CBool,
+        cs::{CS, RCS},
+    },
+    core::signal::Signal,
+};
+
+pub fn hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn alloc_bits<C: CS>(cs: &RCS<C>, bits: &[bool]) -> Vec<CBool<C>> {
+    bits.iter().map(|b| CBool::alloc(cs, Some(b))).collect()
+}
+
+pub fn bit_values<C: CS>(bits: &[CBool<C>]) -> Vec<bool> {
+    bits.iter().map(|b| b.get_value().unwrap()).collect()
+}
+
+// Number of the constraints added by the gadget
+pub fn num_gates<C: CS, R>(cs: &RCS<C>, gadget: impl FnOnce() -> R) -> usize {
+    let n = cs.borrow().num_gates();
+    gadget();
+    cs.borrow().num_gates() - n
+}
@@ -1,17 +1,39 @@
 extern crate proc_macro;
-use proc_macro2::{TokenStream/*, Span*/};
-use quote::quote;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
 use syn::{
-    parse_str, Data, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed, Ident, Path, Type/*, Variant*/
+    parse_quote, parse_str, Data, DataEnum, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed, GenericArgument,
+    GenericParam, Ident, Path, PathArguments, Type
 };
 
 
-#[proc_macro_derive(Signal, attributes(Field, Value))]
+#[proc_macro_derive(Signal, attributes(Field, Value, GenerateValue, Discriminant, public, signal))]
 pub fn signal_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = syn::parse(input).unwrap();
     expand(&ast, "Signal").into()
 }
 
+// Root of the fawkes-crypto paths in the generated code, so the code does not depend on the imports of the user.
+// The crate refers to itself with the same name. If the dependency is renamed, the path is set with
+// #[signal(crate = "path")].
+fn krate(attrs: &[syn::Attribute]) -> TokenStream {
+    let usage = "attribute signal should be #[signal(crate = \"path\")]";
+    match attrs.iter().find(|a| a.path.is_ident("signal")).map(|a| a.parse_meta()) {
+        None => quote!(::fawkes_crypto_zkbob),
+        Some(Ok(syn::Meta::List(list))) if list.nested.len() == 1 => match list.nested.first() {
+            Some(syn::NestedMeta::Meta(syn::Meta::NameValue(nv))) if nv.path.is_ident("crate") => match nv.lit {
+                syn::Lit::Str(ref s) => {
+                    let path = s.parse::<Path>().expect("crate should be a path");
+                    quote!(#path)
+                }
+                _ => panic!("crate should be a string"),
+            },
+            _ => panic!("{}", usage),
+        },
+        _ => panic!("{}", usage),
+    }
+}
+
 fn field_idents<'a>(fields: &'a [&'a Field]) -> Vec<&'a Ident> {
     fields
         .iter()
@@ -37,9 +59,10 @@ fn fetch_attr(name: &str, attrs: &[syn::Attribute]) -> Option<String> {
                         }
                     }
                 }
-                _ => {
+                ref meta if meta.path().is_ident(name) => {
                     panic!("attribute {} should be a string", name);
                 }
+                _ => {}
             }
         }
     }
@@ -47,6 +70,35 @@ fn fetch_attr(name: &str, attrs: &[syn::Attribute]) -> Option<String> {
     None
 }
 
+// Indices of the fields marked with #[public] or #[public = "N"], in the order of the public inputs.
+// Without explicit positions the fields go in the order of declaration.
+fn public_fields(fields: &[&Field]) -> Vec<usize> {
+    let mut res = vec![];
+    for (i, f) in fields.iter().enumerate() {
+        for attr in f.attrs.iter().filter(|a| a.path.is_ident("public")) {
+            let position = match attr.parse_meta() {
+                Ok(syn::Meta::Path(_)) => None,
+                Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(ref s), .. })) => {
+                    Some(s.value().parse::<usize>().expect("public position should be a number"))
+                }
+                _ => panic!("attribute public should be #[public] or #[public = \"N\"]"),
+            };
+            res.push((position, i));
+        }
+    }
+
+    if res.iter().any(|(p, _)| p.is_some()) {
+        if res.iter().any(|(p, _)| p.is_none()) {
+            panic!("either all or none of the public fields should have positions");
+        }
+        res.sort_by_key(|(p, _)| p.unwrap());
+        if res.windows(2).any(|w| w[0].0 == w[1].0) {
+            panic!("public positions should be unique");
+        }
+    }
+    res.into_iter().map(|(_, i)| i).collect()
+}
+
 fn unnamed_to_vec(fields: &FieldsUnnamed) -> Vec<&Field> {
     fields.unnamed.iter().collect()
 }
@@ -56,41 +108,235 @@ fn named_to_vec(fields: &FieldsNamed) -> Vec<&Field> {
 }
 
 fn expand(input: &DeriveInput, _: &str) -> TokenStream {
+    if let Data::Enum(ref data_enum) = input.data {
+        return enum_derive(input, data_enum);
+    }
+    let krate = &krate(&input.attrs);
     let input_type = &input.ident;
-    let value_type = parse_str::<Type>(
-        &fetch_attr("Value", &input.attrs).expect("attribute value should be defined"),
-    )
-    .expect("attribute should be a type");
-
     let cs_path = parse_str::<Path>(&fetch_attr("CS", &input.attrs).unwrap_or(String::from("C"))).expect("attribute should be a path");
 
+    let (native_value, value_type) = match fetch_attr("GenerateValue", &input.attrs) {
+        Some(name) => native_value_impl(input, &name, &cs_path, krate),
+        None => {
+            let value_type = parse_str::<Type>(
+                &fetch_attr("Value", &input.attrs).expect("attribute value should be defined"),
+            )
+            .expect("attribute should be a type");
+            (quote!(), value_type)
+        }
+    };
+
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut public = vec![];
+    let mut public_types = vec![];
     let body = match input.data {
         Data::Struct(ref data_struct) => match data_struct.fields {
             Fields::Unnamed(ref fields) => {
                 let field_vec = unnamed_to_vec(fields);
-                tuple_impl(&field_vec, &cs_path)
+                public_types = public_fields(&field_vec).into_iter().map(|i| &field_vec[i].ty).collect();
+                public = public_fields(&field_vec)
+                    .into_iter()
+                    .map(|i| {
+                        let i = syn::Index::from(i);
+                        quote!(#i)
+                    })
+                    .collect();
+                tuple_impl(&field_vec, &cs_path, krate)
             }
             Fields::Named(ref fields) => {
                 let field_vec = named_to_vec(fields);
-                struct_impl(&field_vec, &cs_path)
+                let names = field_idents(&field_vec);
+                public_types = public_fields(&field_vec).into_iter().map(|i| &field_vec[i].ty).collect();
+                public = public_fields(&field_vec)
+                    .into_iter()
+                    .map(|i| {
+                        let name = names[i];
+                        quote!(#name)
+                    })
+                    .collect();
+                struct_impl(&field_vec, &cs_path, krate)
             }
             Fields::Unit => panic!("Unit structs not supported"),
         },
-        // Data::Enum(ref data_enum) => {
-        //     enum_impl(data_enum.variants.iter().cloned().collect(), &cs_path)
-        // },
-        _ => panic!("Only structs can derive a constructor"),
+        _ => panic!("Only structs and enums can derive a constructor"),
+    };
+
+    // the public part is implemented only for the structs with #[public] fields
+    let public_impl = if public.is_empty() {
+        quote!()
+    } else {
+        let signal = quote!(#krate::core::signal::Signal<#cs_path>);
+        let num = quote!(#krate::ff_uint::Num<<#cs_path as #krate::circuit::cs::CS>::Fr>);
+        quote! {
+            impl #impl_generics #krate::core::signal::PublicSignal<#cs_path> for #input_type#ty_generics #where_clause {
+                type PublicValue = (#(<#public_types as #signal>::Value,)*);
+
+                fn inputize_public(&self) {
+                    #(<#public_types as #signal>::inputize(&self.#public);)*
+                }
+
+                fn public_value(value: &Self::Value) -> Self::PublicValue {
+                    (#(value.#public.clone(),)*)
+                }
+
+                fn write_public_inputs(value: &Self::PublicValue, inputs: &mut Vec<#num>) {
+                    <(#(#public_types,)*) as #signal>::write_inputs(value, inputs);
+                }
+            }
+        }
     };
 
     quote! {
+        #native_value
+
         impl #impl_generics Signal<#cs_path> for #input_type#ty_generics #where_clause {
             type Value = #value_type;
 
             #body
 
         }
+
+        #public_impl
+    }
+}
+
+// Native type of the field: CNum -> Num, CBool -> bool, SizedVec<T, L> -> SizedVec<T::Value, L>, tuples are mapped elementwise.
+// Other types should be set with #[Value = "..."] attribute on the field.
+fn native_type(ty: &Type, krate: &TokenStream) -> TokenStream {
+    match ty {
+        Type::Path(p) => {
+            let segment = p.path.segments.last().unwrap();
+            match segment.ident.to_string().as_str() {
+                "CNum" => {
+                    quote!(#krate::ff_uint::Num<Fr>)
+                }
+                "CBool" => quote!(bool),
+                "SizedVec" => match segment.arguments {
+                    PathArguments::AngleBracketed(ref args) => match (args.args.first(), args.args.last()) {
+                        (Some(GenericArgument::Type(t)), Some(l)) if args.args.len() == 2 => {
+                            let t = native_type(t, krate);
+                            quote!(#krate::core::sizedvec::SizedVec<#t, #l>)
+                        }
+                        _ => panic!("SizedVec should have a type and a length"),
+                    },
+                    _ => panic!("SizedVec should have a type and a length"),
+                },
+                _ => panic!("type {} is not supported by GenerateValue, set Value attribute on the field", quote!(#ty)),
+            }
+        }
+        Type::Tuple(t) => {
+            let elems = t.elems.iter().map(|t| native_type(t, krate));
+            quote!((#(#elems,)*))
+        }
+        _ => panic!("type {} is not supported by GenerateValue, set Value attribute on the field", quote!(#ty)),
+    }
+}
+
+fn native_field_type(field: &Field, krate: &TokenStream) -> TokenStream {
+    match fetch_attr("Value", &field.attrs) {
+        Some(t) => {
+            let t = parse_str::<Type>(&t).expect("attribute should be a type");
+            quote!(#t)
+        }
+        None => native_type(&field.ty, krate),
+    }
+}
+
+// #[GenerateValue = "Name"] generates the native struct Name<Fr, ...> with the same fields and generic parameters,
+// except the CS parameter, so the Value attribute is not needed
+fn native_value_impl(input: &DeriveInput, name: &str, cs_path: &Path, krate: &TokenStream) -> (TokenStream, Type) {
+    let cs_ident = cs_path.get_ident().expect("GenerateValue requires CS to be a generic parameter");
+    let vis = &input.vis;
+    let name = format_ident!("{}", name);
+
+    let params = input
+        .generics
+        .params
+        .iter()
+        .filter(|p| !matches!(p, GenericParam::Type(t) if &t.ident == cs_ident))
+        .collect::<Vec<_>>();
+    if params.len() == input.generics.params.len() {
+        panic!("GenerateValue requires CS to be a generic parameter");
     }
+    let args = params
+        .iter()
+        .map(|p| match p {
+            GenericParam::Type(t) => {
+                let ident = &t.ident;
+                quote!(#ident)
+            }
+            GenericParam::Lifetime(l) => {
+                let lifetime = &l.lifetime;
+                quote!(#lifetime)
+            }
+            GenericParam::Const(c) => {
+                let ident = &c.ident;
+                quote!(#ident)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let borsh = quote!(#krate::borsh);
+    let (body, types, serialize, deserialize) = match input.data {
+        Data::Struct(ref data_struct) => match data_struct.fields {
+            Fields::Named(ref fields) => {
+                let field_vec = named_to_vec(fields);
+                let names = field_idents(&field_vec);
+                let types = field_vec.iter().map(|f| native_field_type(f, krate)).collect::<Vec<_>>();
+                (
+                    quote!({ #(pub #names: #types,)* }),
+                    types,
+                    quote!(#(#borsh::BorshSerialize::serialize(&self.#names, writer)?;)*),
+                    quote!(Self { #(#names: #borsh::BorshDeserialize::deserialize(buf)?),* }),
+                )
+            }
+            Fields::Unnamed(ref fields) => {
+                let types = fields.unnamed.iter().map(|f| native_field_type(f, krate)).collect::<Vec<_>>();
+                let ids = (0..types.len()).map(syn::Index::from);
+                let des = types.iter().map(|_| quote!(#borsh::BorshDeserialize::deserialize(buf)?));
+                (
+                    quote!((#(pub #types,)*);),
+                    types.clone(),
+                    quote!(#(#borsh::BorshSerialize::serialize(&self.#ids, writer)?;)*),
+                    quote!(Self(#(#des),*)),
+                )
+            }
+            Fields::Unit => panic!("Unit structs not supported"),
+        },
+        _ => panic!("GenerateValue is supported only for structs"),
+    };
+
+    // serde and borsh support follows the features of fawkes-crypto, not of the crate with the derive,
+    // so the helper macros of fawkes-crypto add the implementations
+    let serde = format!("{}::serde", krate).replace(' ', "");
+    let native = quote! {
+        #krate::__derive_serde! {
+            #serde,
+            #[derive(Clone, Debug)]
+            #vis struct #name<Fr: #krate::ff_uint::PrimeField, #(#params),*> #body
+        }
+
+        #krate::__borsh_support! {
+            impl<Fr: #krate::ff_uint::PrimeField, #(#params),*> #borsh::BorshSerialize for #name<Fr, #(#args),*>
+            where #(#types: #borsh::BorshSerialize),*
+            {
+                fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                    #serialize
+                    Ok(())
+                }
+            }
+
+            impl<Fr: #krate::ff_uint::PrimeField, #(#params),*> #borsh::BorshDeserialize for #name<Fr, #(#args),*>
+            where #(#types: #borsh::BorshDeserialize),*
+            {
+                fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                    Ok(#deserialize)
+                }
+            }
+        }
+    };
+    let value_type = parse_quote!(#name<#cs_ident::Fr, #(#args),*>);
+    (native, value_type)
 }
 
 fn get_field_types_iter<'a>(fields: &'a [&'a Field]) -> Box<dyn Iterator<Item = &'a Type> + 'a> {
@@ -103,7 +349,7 @@ fn get_field_types<'a>(fields: &'a [&'a Field]) -> Vec<&'a Type> {
 
 
 
-fn tuple_impl(fields: &[&Field], cs_path:&Path) -> TokenStream {
+fn tuple_impl(fields: &[&Field], cs_path:&Path, krate: &TokenStream) -> TokenStream {
     let var_typenames = get_field_types(&fields);
     let var_ids = (0..fields.len())
         .map(|i| syn::Index::from(i))
@@ -154,10 +400,18 @@ fn tuple_impl(fields: &[&Field], cs_path:&Path) -> TokenStream {
         fn alloc(cs:&RCS<#cs_path>, value:Option<&Self::Value>) -> Self {
             Self(#(<#var_typenames>::alloc(cs, value.map(|v| &v.#var_ids))),*)
         }
+
+        fn write_inputs(value: &Self::Value, inputs: &mut Vec<#krate::ff_uint::Num<<#cs_path as #krate::circuit::cs::CS>::Fr>>) {
+            #(<#var_typenames>::write_inputs(&value.#var_ids, inputs);)*
+        }
+
+        fn read_inputs(inputs: &mut &[#krate::ff_uint::Num<<#cs_path as #krate::circuit::cs::CS>::Fr>]) -> Option<Self::Value> {
+            Some(Self::Value{#(#var_ids: <#var_typenames>::read_inputs(inputs)?),*})
+        }
     }
 }
 
-fn struct_impl(fields: &[&Field], cs_path:&Path) -> TokenStream {
+fn struct_impl(fields: &[&Field], cs_path:&Path, krate: &TokenStream) -> TokenStream {
     let var_typenames = get_field_types(&fields);
     let var_names: &Vec<Ident> = &field_idents(fields).iter().map(|f| (**f).clone()).collect();
 
@@ -208,160 +462,313 @@ fn struct_impl(fields: &[&Field], cs_path:&Path) -> TokenStream {
             Self {#(#var_names: <#var_typenames>::alloc(cs, value.map(|v| &v.#var_names))),*}
         }
 
+        fn write_inputs(value: &Self::Value, inputs: &mut Vec<#krate::ff_uint::Num<<#cs_path as #krate::circuit::cs::CS>::Fr>>) {
+            #(<#var_typenames>::write_inputs(&value.#var_names, inputs);)*
+        }
+
+        fn read_inputs(inputs: &mut &[#krate::ff_uint::Num<<#cs_path as #krate::circuit::cs::CS>::Fr>]) -> Option<Self::Value> {
+            Some(Self::Value {#(#var_names: <#var_typenames>::read_inputs(inputs)?),*})
+        }
+
 
     }
 }
 
-/* 
-fn enum_impl(variants: Vec<Variant>, cs_path: &Path) -> TokenStream {
-    let variant_names: Vec<Ident> = variants.iter().map(|v| v.ident.clone()).collect();
+fn to_snake_case(name: &str) -> String {
+    let mut res = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                res.push('_');
+            }
+            res.extend(c.to_lowercase());
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
 
-    // original field names
-    let field_names: Vec<Vec<TokenStream>> = variants
-        .iter()
-        .map(|variant| {
-            if variant.fields.is_empty() {
-                panic!("Variants cannot be empty");
-            }
-
-            variant.fields
-                .iter()
-                .enumerate()
-                .map(|(i, f)| f.ident
-                    .clone()
-                    .map(|ident| quote!(#ident))
-                    .unwrap_or_else(|| {
-                        let i = syn::Index::from(i);
-                        quote!(#i)
-                    })
-                )
-                .collect()
-        })
-        .collect();
 
-    let field_names_str: Vec<Vec<String>> = field_names
-        .iter()
-        .map(|fields| {
-            fields.iter().map(|field| field.to_string()).collect()
+// The circuit can not hold only one variant of the enum, because the variant is a part of the witness,
+// and the layout of the circuit should not depend on it. So the signal of the enum is the tuple struct:
+// the discriminant is the field 0, then the fields of the variants with data follow as tuples,
+// in the order of declaration. The fields of inactive variants are constrained to the default values,
+// so the values of all variant fields should implement Default.
+//
+// #[derive(Signal)] keeps the enum as the declaration and generates the struct with Signal suffix,
+// #[Discriminant = "binary"] selects binary encoding of the discriminant instead of one-hot:
+//
+// #[derive(Signal)]
+// #[Value = "Tx<C::Fr>"]
+// pub enum CTx<C: CS> { Deposit(CNum<C>), Noop }
+//
+// generates pub struct CTxSignal<C: CS>.
+fn enum_derive(input: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
+    let binary = match fetch_attr("Discriminant", &input.attrs).as_deref() {
+        None | Some("one_hot") => false,
+        Some("binary") => true,
+        Some(other) => panic!("unknown discriminant encoding {}", other),
+    };
+    let name = format_ident!("{}Signal", input.ident);
+    let attrs = quote!(#[derive(Clone)]);
+    enum_impl(input, data_enum, &name, attrs, &cs_param(&input.generics), binary)
+}
+
+// #[enum_signal] replaces the enum with the struct of the same name, the attributes of the enum go to the struct.
+// #[enum_signal(binary)] selects binary encoding of the discriminant instead of one-hot.
+#[proc_macro_attribute]
+pub fn enum_signal(attr: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let binary = match attr.to_string().as_str() {
+        "" | "one_hot" => false,
+        "binary" => true,
+        other => panic!("unknown discriminant encoding {}", other),
+    };
+    let ast: DeriveInput = syn::parse(input).unwrap();
+    let cs_path = cs_param(&ast.generics);
+    let attrs = ast.attrs.iter().filter(|a| !a.path.is_ident("Value") && !a.path.is_ident("signal"));
+    let attrs = quote!(#(#attrs)*);
+    match ast.data {
+        Data::Enum(ref data_enum) => enum_impl(&ast, data_enum, &ast.ident, attrs, &cs_path, binary).into(),
+        _ => panic!("enum_signal is supported only for enums"),
+    }
+}
+
+// The generic parameter bounded by CS
+fn cs_param(generics: &syn::Generics) -> Path {
+    generics
+        .type_params()
+        .find(|p| {
+            p.bounds.iter().any(|b| match b {
+                syn::TypeParamBound::Trait(t) => matches!(t.path.segments.last(), Some(s) if s.ident == "CS"),
+                _ => false,
+            })
         })
-        .collect();
+        .map(|p| Path::from(p.ident.clone()))
+        .expect("signal enum should have a generic parameter bounded by CS")
+}
 
-    // prefixed field names (for referencing tuple members)
-    let field_names_re: Vec<Vec<Ident>> = field_names_str.iter()
-        .map(|fields| fields.iter().map(|field| Ident::new(&format!("__first_{}", field), Span::call_site())).collect())
-        .collect();
+fn enum_impl(
+    input: &DeriveInput,
+    data_enum: &DataEnum,
+    name: &Ident,
+    attrs: TokenStream,
+    cs_path: &Path,
+    binary: bool,
+) -> TokenStream {
+    let krate = &krate(&input.attrs);
+    let value_type = &parse_str::<Type>(&fetch_attr("Value", &input.attrs).expect("attribute value should be defined"))
+        .expect("attribute should be a type");
+    let signal = quote!(#krate::core::signal::Signal<#cs_path>);
+    let cbool = quote!(#krate::circuit::bool::CBool<#cs_path>);
+    let rcs = quote!(#krate::circuit::cs::RCS<#cs_path>);
+    let num = quote!(#krate::ff_uint::Num<<#cs_path as #krate::circuit::cs::CS>::Fr>);
+
+    let vis = &input.vis;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // the value enum is referenced without generic arguments, they are inferred
+    let mut value_path = match value_type {
+        Type::Path(p) => p.path.clone(),
+        _ => panic!("attribute value should be a path to the enum"),
+    };
+    value_path.segments.last_mut().unwrap().arguments = syn::PathArguments::None;
 
-    // for destructuring the second argument
-    let other_field_names: Vec<Vec<Ident>> = field_names_str.iter().map(|fields| {
-        fields.iter().map(|field| {
-            Ident::new(&format!("__second_{}", field), Span::call_site())
-        }).collect()
-    }).collect();
+    let variants = data_enum.variants.iter().collect::<Vec<_>>();
+    if variants.is_empty() {
+        panic!("Enums without variants not supported");
+    }
+    let num_variants = variants.len();
+    let discriminant = quote!(#krate::circuit::discriminant::CDiscriminant<#cs_path, #num_variants, #binary>);
+
+    let mut field_ids = vec![];
+    let mut read_fields = vec![];
+    let mut field_types = vec![];
+    let mut field_indices = vec![];
+    let mut is_methods = vec![];
+    let mut index_arms = vec![];
+    let mut value_arms = vec![];
+    let mut read_arms = vec![];
+    let mut const_arms = vec![];
+    let mut field_values = vec![];
+    let mut assert_const_arms = vec![];
+
+    for (index, variant) in variants.iter().enumerate() {
+        let variant_name = &variant.ident;
+        let is_method = format_ident!("is_{}", to_snake_case(&variant_name.to_string()));
+        if is_method == "is_eq" {
+            panic!("variant {} conflicts with Signal::is_eq", variant_name);
+        }
+        is_methods.push(quote! {
+            pub fn #is_method(&self) -> #cbool {
+                self.0.is_variant(#index)
+            }
+        });
 
-    let first_field_names = field_names_re.iter().map(|names| names[0].clone());
+        let bindings = (0..variant.fields.len())
+            .map(|i| format_ident!("__v{}", i))
+            .collect::<Vec<_>>();
+        let pattern = match variant.fields {
+            Fields::Unnamed(_) => quote!(#value_path::#variant_name(#(#bindings),*)),
+            Fields::Named(ref fields) => {
+                let names = named_to_vec(fields);
+                let names = field_idents(&names);
+                quote!(#value_path::#variant_name{#(#names: #bindings),*})
+            }
+            Fields::Unit => quote!(#value_path::#variant_name),
+        };
+        let wildcard = match variant.fields {
+            Fields::Unnamed(_) => quote!(#value_path::#variant_name(..)),
+            Fields::Named(_) => quote!(#value_path::#variant_name{..}),
+            Fields::Unit => quote!(#value_path::#variant_name),
+        };
+        index_arms.push(quote!(#wildcard => #index));
+
+        if variant.fields.is_empty() {
+            read_arms.push(quote!(#index => Some(#pattern)));
+            value_arms.push(quote!(#index => Some(#pattern)));
+            const_arms.push(quote!(#index => Some(#pattern)));
+            assert_const_arms.push(quote!(#pattern => {}));
+            continue;
+        }
+
+        let field = syn::Index::from(field_ids.len() + 1);
+        let types = variant.fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+        value_arms.push(quote! {
+            #index => {
+                let (#(#bindings,)*) = #krate::core::signal::Signal::get_value(&self.#field)?;
+                Some(#pattern)
+            }
+        });
+        const_arms.push(quote! {
+            #index => {
+                let (#(#bindings,)*) = #krate::core::signal::Signal::as_const(&self.#field)?;
+                Some(#pattern)
+            }
+        });
+        let read_field = format_ident!("__f{}", field_ids.len() + 1);
+        read_arms.push(quote! {
+            #index => {
+                let (#(#bindings,)*) = #read_field;
+                Some(#pattern)
+            }
+        });
+        field_values.push(quote! {
+            |value: &Self::Value| match value {
+                #pattern => (#(#bindings.clone(),)*),
+                #[allow(unreachable_patterns)]
+                _ => Default::default(),
+            }
+        });
+        assert_const_arms.push(quote! {
+            #pattern => #krate::core::signal::Signal::assert_const(&self.#field, &(#(#bindings.clone(),)*))
+        });
+        field_ids.push(field);
+        read_fields.push(read_field);
+        field_types.push(quote!((#(#types,)*)));
+        field_indices.push(index);
+    }
 
     quote! {
-        fn get_value(&self) -> Option<Self::Value> {
-            match self {
-                #(Self::#variant_names { #(#field_names: #field_names_re),* } => {
-                    Some(Self::Value::#variant_names {#(#field_names: #field_names_re.get_value()?),*})
-                }),*
+        #attrs
+        #vis struct #name #generics (pub #discriminant, #(pub #field_types),*) #where_clause;
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#is_methods)*
+
+            fn variant_index(value: &#value_type) -> usize {
+                match value {
+                    #(#index_arms,)*
+                }
             }
         }
 
-        fn as_const(&self) -> Option<Self::Value> {
-            match self {
-                #(Self::#variant_names { #(#field_names: #field_names_re),* } => {
-                    Some(Self::Value::#variant_names {#(#field_names: #field_names_re.as_const()?),*})
-                }),*
+        impl #impl_generics #signal for #name #ty_generics #where_clause {
+            type Value = #value_type;
+
+            fn get_value(&self) -> Option<Self::Value> {
+                match #krate::core::signal::Signal::get_value(&self.0)? {
+                    #(#value_arms,)*
+                    _ => None,
+                }
             }
-        }
 
-        fn switch(&self, bit: &CBool<#cs_path>, if_else: &Self) -> Self {
-            match self {
-                #(Self::#variant_names { #(#field_names: #field_names_re),* } => {
-                    if let Self::#variant_names { #(#field_names: #other_field_names),* } = if_else {
-                        Self::#variant_names {#(#field_names: #field_names_re.switch(bit, #other_field_names)),*}
-                    } else {
-                        panic!("Signal::switch: variants do not match");
-                    }
-                }),*
+            fn as_const(&self) -> Option<Self::Value> {
+                match #krate::core::signal::Signal::as_const(&self.0)? {
+                    #(#const_arms,)*
+                    _ => None,
+                }
             }
-        }
 
-        fn get_cs(&self) -> &RCS<#cs_path> {
-            match self {
-                #(Self::#variant_names { #(#field_names: #field_names_re),* } => {
-                    #first_field_names.get_cs()
-                }),*
+            fn switch(&self, bit: &#cbool, if_else: &Self) -> Self {
+                Self(
+                    #krate::core::signal::Signal::switch(&self.0, bit, &if_else.0),
+                    #(#krate::core::signal::Signal::switch(&self.#field_ids, bit, &if_else.#field_ids),)*
+                )
             }
-        }
 
-        fn from_const(cs:&RCS<#cs_path>, value: &Self::Value) -> Self {
-            match value {
-                #(Self::Value::#variant_names { #(#field_names: #field_names_re),* } => {
-                    Self::#variant_names {#(#field_names: Signal::from_const(cs, #field_names_re)),*}
-                }),*
+            fn get_cs(&self) -> &#rcs {
+                #krate::core::signal::Signal::get_cs(&self.0)
             }
-        }
 
-        fn assert_const(&self, value: &Self::Value) {
-            match self {
-                #(Self::#variant_names { #(#field_names: #field_names_re),* } => {
-                    if let Self::Value::#variant_names { #(#field_names: #other_field_names),* } = value {
-                        #(#field_names_re.assert_const(#other_field_names);)*
-                    } else {
-                        panic!("Signal::assert_const: variants do not match");
-                    }
-                }),*
+            fn from_const(cs: &#rcs, value: &Self::Value) -> Self {
+                Self(
+                    <#discriminant as #signal>::from_const(cs, &Self::variant_index(value)),
+                    #(<#field_types as #signal>::from_const(cs, &(#field_values)(value)),)*
+                )
             }
-        }
 
-        fn inputize(&self) {
-            match self {
-                #(Self::#variant_names { #(#field_names: #field_names_re),* } => {
-                    #(#field_names_re.inputize();)*
-                }),*
+            fn assert_const(&self, value: &Self::Value) {
+                #krate::core::signal::Signal::assert_const(&self.0, &Self::variant_index(value));
+                match value {
+                    #(#assert_const_arms,)*
+                }
             }
-        }
 
-        fn assert_eq(&self, other: &Self) {
-            match self {
-                #(Self::#variant_names { #(#field_names: #field_names_re),* } => {
-                    if let Self::#variant_names { #(#field_names: #other_field_names),* } = other {
-                        #(#field_names_re.assert_eq(#other_field_names);)*
-                    } else {
-                        panic!("Signal::assert_eq: variants do not match");
-                    }
-                }),*
+            fn inputize(&self) {
+                #krate::core::signal::Signal::inputize(&self.0);
+                #(#krate::core::signal::Signal::inputize(&self.#field_ids);)*
             }
-        }
 
-        fn is_eq(&self, other: &Self) -> CBool<#cs_path> {
-            let mut acc = self.derive_const(&true);
+            // the fields of inactive variants are equal to the defaults, so they are compared as well
+            fn assert_eq(&self, other: &Self) {
+                #krate::core::signal::Signal::assert_eq(&self.0, &other.0);
+                #(#krate::core::signal::Signal::assert_eq(&self.#field_ids, &other.#field_ids);)*
+            }
 
-            match self {
-                #(Self::#variant_names { #(#field_names: #field_names_re),* } => {
-                    if let Self::#variant_names { #(#field_names: #other_field_names),* } = other {
-                        #(acc &= #field_names_re.is_eq(#other_field_names);)*
-                    }
-                }),*
+            fn is_eq(&self, other: &Self) -> #cbool {
+                let mut acc = #krate::core::signal::Signal::is_eq(&self.0, &other.0);
+                #(acc &= &#krate::core::signal::Signal::is_eq(&self.#field_ids, &other.#field_ids);)*
+                acc
             }
 
-            acc
-        }
+            // the variables are allocated in the same order for any value
+            fn alloc(cs: &#rcs, value: Option<&Self::Value>) -> Self {
+                let discriminant = <#discriminant as #signal>::alloc(cs, value.map(Self::variant_index).as_ref());
+                #(let #read_fields = {
+                    let field = <#field_types as #signal>::alloc(cs, value.map(#field_values).as_ref());
+                    let default = <#field_types as #signal>::from_const(cs, &Default::default());
+                    let active = discriminant.is_variant(#field_indices);
+                    #krate::core::signal::Signal::assert_eq(&field, &#krate::core::signal::Signal::switch(&field, &active, &default));
+                    field
+                };)*
+                Self(discriminant, #(#read_fields),*)
+            }
 
-        fn alloc(cs: &RCS<#cs_path>, value: Option<&Self::Value>) -> Self {
-            if let Some(value) = value {
-                match value {
-                    #(Self::Value::#variant_names { #(#field_names: #field_names_re),* } => {
-                        Self::#variant_names { #(#field_names: Signal::alloc(cs, Some(#field_names_re))),* }
-                    }),*
+            fn write_inputs(value: &Self::Value, inputs: &mut Vec<#num>) {
+                <#discriminant as #signal>::write_inputs(&Self::variant_index(value), inputs);
+                #(<#field_types as #signal>::write_inputs(&(#field_values)(value), inputs);)*
+            }
+
+            fn read_inputs(inputs: &mut &[#num]) -> Option<Self::Value> {
+                let index = <#discriminant as #signal>::read_inputs(inputs)?;
+                #(let #read_fields = <#field_types as #signal>::read_inputs(inputs)?;)*
+                match index {
+                    #(#read_arms,)*
+                    _ => None,
                 }
-            } else {
-                panic!("Signal::alloc on enums requires value to not be empty");
             }
         }
     }
 }
-*/
\ No newline at end of file
@@ -421,6 +421,13 @@ impl<Fp: PrimeField> core::fmt::Debug for Num<Fp> {
     }
 }
 
+impl<Fp: PrimeField> Default for Num<Fp> {
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
 impl<Fp: PrimeField> core::fmt::Display for Num<Fp> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         core::fmt::Display::fmt(&self.0, f)